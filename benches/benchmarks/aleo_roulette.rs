@@ -109,7 +109,7 @@ cfg_if::cfg_if! {
             let mut casino_token_record_data = jaleo::RecordEntriesMap::new();
             casino_token_record_data.insert(
                 "amount".to_owned(),
-                lambdavm::jaleo::UserInputValueType::U64(casino_token_record_amount),
+                lambdavm::jaleo::UserInputValueType::U64(casino_token_record_amount).into(),
             );
             let casino_token_record_nonce = random_nonce();
 
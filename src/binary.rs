@@ -0,0 +1,175 @@
+//! Compact binary encoding of program builds, deployments and transitions.
+//!
+//! Every file starts with `MAGIC`, a byte telling what it holds and the
+//! version of the format. Keys and proofs are written with their compressed
+//! `CanonicalSerialize` encoding straight to the file, so they are neither hex
+//! encoded nor held twice in memory. Identifiers and programs are written as
+//! length prefixed strings, lengths being little endian `u64`s.
+
+use anyhow::{anyhow, ensure, Result};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+const MAGIC: [u8; 4] = *b"LVMB";
+/// Version of the binary format.
+pub const BINARY_FORMAT_VERSION: u32 = 1;
+
+/// What a binary file holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Kind {
+    ProgramBuild,
+    Deployment,
+    Transition,
+}
+
+impl Kind {
+    fn tag(self) -> u8 {
+        match self {
+            Kind::ProgramBuild => 1,
+            Kind::Deployment => 2,
+            Kind::Transition => 3,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::ProgramBuild => "program build",
+            Kind::Deployment => "deployment",
+            Kind::Transition => "transition",
+        }
+    }
+}
+
+pub(crate) fn write_header<W: Write>(writer: &mut W, kind: Kind) -> Result<()> {
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&[kind.tag()])?;
+    writer.write_all(&BINARY_FORMAT_VERSION.to_le_bytes())?;
+    Ok(())
+}
+
+pub(crate) fn read_header<R: Read>(reader: &mut R, kind: Kind) -> Result<()> {
+    let mut magic = [0_u8; 4];
+    reader.read_exact(&mut magic)?;
+    ensure!(magic == MAGIC, "Not a binary {}", kind.name());
+    let mut tag = [0_u8; 1];
+    reader.read_exact(&mut tag)?;
+    ensure!(
+        tag == [kind.tag()],
+        "Expected a binary {} but got something else",
+        kind.name()
+    );
+    let mut version = [0_u8; 4];
+    reader.read_exact(&mut version)?;
+    let version = u32::from_le_bytes(version);
+    ensure!(
+        version == BINARY_FORMAT_VERSION,
+        "Unsupported binary {} version {version}, expected {BINARY_FORMAT_VERSION}",
+        kind.name()
+    );
+    Ok(())
+}
+
+/// Whether the file is in the binary format rather than JSON.
+pub(crate) fn is_binary_file(path: &Path) -> Result<bool> {
+    let mut magic = Vec::with_capacity(MAGIC.len());
+    open_file(path)?
+        .take(u64::try_from(MAGIC.len())?)
+        .read_to_end(&mut magic)?;
+    Ok(magic == MAGIC)
+}
+
+pub(crate) fn open_file(path: &Path) -> Result<BufReader<File>> {
+    let file = File::open(path).map_err(|e| anyhow!("Error reading {}: {e}", path.display()))?;
+    Ok(BufReader::new(file))
+}
+
+pub(crate) fn create_file(path: &Path) -> Result<BufWriter<File>> {
+    let file = File::create(path).map_err(|e| anyhow!("Error writing {}: {e}", path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+pub(crate) fn write_length<W: Write>(writer: &mut W, length: usize) -> Result<()> {
+    writer.write_all(&u64::try_from(length)?.to_le_bytes())?;
+    Ok(())
+}
+
+pub(crate) fn read_length<R: Read>(reader: &mut R) -> Result<usize> {
+    let mut length = [0_u8; 8];
+    reader.read_exact(&mut length)?;
+    Ok(usize::try_from(u64::from_le_bytes(length))?)
+}
+
+pub(crate) fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<()> {
+    write_length(writer, bytes.len())?;
+    writer.write_all(bytes)?;
+    Ok(())
+}
+
+pub(crate) fn read_bytes<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+    let length = read_length(reader)?;
+    // Read through `take` so a corrupted length does not allocate it all at once.
+    let mut bytes = Vec::new();
+    reader
+        .take(u64::try_from(length)?)
+        .read_to_end(&mut bytes)?;
+    ensure!(bytes.len() == length, "Unexpected end of binary file");
+    Ok(bytes)
+}
+
+pub(crate) fn write_string<W: Write>(writer: &mut W, string: &str) -> Result<()> {
+    write_bytes(writer, string.as_bytes())
+}
+
+pub(crate) fn read_string<R: Read>(reader: &mut R) -> Result<String> {
+    Ok(String::from_utf8(read_bytes(reader)?)?)
+}
+
+pub(crate) fn write_canonical<W: Write, T: CanonicalSerialize>(
+    writer: &mut W,
+    value: &T,
+) -> Result<()> {
+    value.serialize(&mut *writer)?;
+    Ok(())
+}
+
+pub(crate) fn read_canonical<R: Read, T: CanonicalDeserialize>(reader: &mut R) -> Result<T> {
+    T::deserialize(&mut *reader).map_err(|e| anyhow!("Error deserializing binary file: {e}"))
+}
+
+#[cfg(test)]
+mod binary_tests {
+    use super::{read_header, read_string, write_header, write_string, Kind};
+
+    #[test]
+    fn test_header_and_string_round_trip() {
+        let mut bytes = Vec::new();
+        write_header(&mut bytes, Kind::Deployment).unwrap();
+        write_string(&mut bytes, "hello_1").unwrap();
+
+        let mut reader = bytes.as_slice();
+        read_header(&mut reader, Kind::Deployment).unwrap();
+        assert_eq!(read_string(&mut reader).unwrap(), "hello_1");
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_header_of_other_kind_is_rejected() {
+        let mut bytes = Vec::new();
+        write_header(&mut bytes, Kind::Transition).unwrap();
+
+        assert!(read_header(&mut bytes.as_slice(), Kind::ProgramBuild).is_err());
+    }
+
+    #[test]
+    fn test_truncated_string_is_rejected() {
+        let mut bytes = Vec::new();
+        write_string(&mut bytes, "hello_1").unwrap();
+        bytes.pop();
+
+        assert!(read_string(&mut bytes.as_slice()).is_err());
+    }
+}
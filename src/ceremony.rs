@@ -0,0 +1,440 @@
+//! Powers of tau style setup ceremony for the universal parameters.
+//!
+//! The parameters hold powers of a secret β in both groups. Each participant
+//! contributes a fresh secret τ, turning β into β·τ, and publishes a
+//! contribution that lets anyone check that the new parameters come from the
+//! previous ones without learning τ. As long as one participant throws their
+//! τ away, nobody knows the final β.
+
+use crate::universal_srs::{self, LoadMode};
+use anyhow::{anyhow, ensure, Result};
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{msm::VariableBaseMSM, AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{Field, PrimeField, UniformRand, Zero};
+use ark_poly_commit::kzg10::UniversalParams;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use simpleworks::marlin::UniversalSRS;
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Everything needed to check a contribution, hex encoded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contribution {
+    /// Checksum of the parameters that were contributed to.
+    pub previous_checksum: String,
+    /// Checksum of the parameters resulting from the contribution.
+    pub checksum: String,
+    /// τ times the generator of G1.
+    pub tau_g1: String,
+    /// τ times the generator of G2.
+    pub tau_g2: String,
+    /// Schnorr proof of knowledge of τ, bound to the previous parameters so
+    /// it cannot be replayed on top of other ones.
+    pub proof_commitment: String,
+    pub proof_response: String,
+}
+
+/// Contributions of a ceremony, in the order they were made.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub contributions: Vec<Contribution>,
+}
+
+impl Transcript {
+    /// Reads a transcript, an empty one if the file does not exist yet.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let serialized_transcript = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Error reading transcript {}: {e}", path.display()))?;
+        Ok(serde_json::from_str(&serialized_transcript)?)
+    }
+
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Position and contribution that turned the parameters with the
+    /// `previous_checksum` into the ones with `checksum`.
+    pub fn find(&self, previous_checksum: &str, checksum: &str) -> Option<(usize, &Contribution)> {
+        self.contributions
+            .iter()
+            .enumerate()
+            .find(|(_, contribution)| {
+                contribution.previous_checksum == previous_checksum
+                    && contribution.checksum == checksum
+            })
+    }
+}
+
+/// Contributes fresh randomness to the universal parameters, returning the
+/// new ones and the contribution that proves they were derived from the
+/// previous ones.
+pub fn contribute<R: RngCore + CryptoRng>(
+    previous: &UniversalSRS,
+    rng: &mut R,
+) -> Result<(UniversalSRS, Contribution)> {
+    let tau = nonzero_scalar(rng);
+    let tau_inverse = tau
+        .inverse()
+        .ok_or_else(|| anyhow!("Error inverting contribution secret"))?;
+    // The powers of γ·g are only used to make commitments hiding, so they
+    // are rerandomized too but only need to stay consistent with β.
+    let gamma_factor = nonzero_scalar(rng);
+
+    let powers_of_g = previous
+        .powers_of_g
+        .iter()
+        .enumerate()
+        .map(|(i, power)| Ok(power.mul(tau.pow([u64::try_from(i)?]).into_repr())))
+        .collect::<Result<Vec<G1Projective>>>()?;
+    let powers_of_gamma_g = previous
+        .powers_of_gamma_g
+        .iter()
+        .map(|(i, power)| {
+            let factor = gamma_factor * tau.pow([u64::try_from(*i)?]);
+            Ok((*i, power.mul(factor.into_repr())))
+        })
+        .collect::<Result<Vec<(usize, G1Projective)>>>()?;
+    let neg_powers_of_h = previous
+        .neg_powers_of_h
+        .iter()
+        .map(|(i, power)| {
+            let factor = tau_inverse.pow([u64::try_from(*i)?]);
+            Ok((*i, power.mul(factor.into_repr())))
+        })
+        .collect::<Result<Vec<(usize, G2Projective)>>>()?;
+    let beta_h = previous.beta_h.mul(tau.into_repr()).into_affine();
+
+    let next = UniversalParams::<Bls12_381> {
+        powers_of_g: G1Projective::batch_normalization_into_affine(&powers_of_g),
+        powers_of_gamma_g: normalize_map(powers_of_gamma_g),
+        h: previous.h,
+        beta_h,
+        neg_powers_of_h: normalize_map(neg_powers_of_h),
+        prepared_h: previous.h.into(),
+        prepared_beta_h: beta_h.into(),
+    };
+
+    let previous_checksum = hex::encode(universal_srs::universal_srs_checksum(previous)?);
+    let g = generator_g1(previous)?;
+    let tau_g1 = g.mul(tau.into_repr()).into_affine();
+    let tau_g2 = previous.h.mul(tau.into_repr()).into_affine();
+    let nonce = nonzero_scalar(rng);
+    let proof_commitment = g.mul(nonce.into_repr()).into_affine();
+    let challenge = proof_challenge(&previous_checksum, &tau_g1, &proof_commitment)?;
+    let proof_response = nonce + challenge * tau;
+
+    let contribution = Contribution {
+        previous_checksum,
+        checksum: hex::encode(universal_srs::universal_srs_checksum(&next)?),
+        tau_g1: to_hex(&tau_g1)?,
+        tau_g2: to_hex(&tau_g2)?,
+        proof_commitment: to_hex(&proof_commitment)?,
+        proof_response: to_hex(&proof_response)?,
+    };
+    Ok((next, contribution))
+}
+
+/// Checks that `next` is the result of `contribution` on top of `previous`,
+/// and that `next` is made of consecutive powers of its secret.
+pub fn verify_contribution(
+    previous: &UniversalSRS,
+    next: &UniversalSRS,
+    contribution: &Contribution,
+) -> Result<()> {
+    ensure!(
+        contribution.previous_checksum
+            == hex::encode(universal_srs::universal_srs_checksum(previous)?),
+        "The contribution was not made to the previous parameters"
+    );
+    ensure!(
+        contribution.checksum == hex::encode(universal_srs::universal_srs_checksum(next)?),
+        "The contribution did not result in the next parameters"
+    );
+    ensure!(
+        next.powers_of_g.len() == previous.powers_of_g.len()
+            && next
+                .powers_of_gamma_g
+                .keys()
+                .eq(previous.powers_of_gamma_g.keys())
+            && next
+                .neg_powers_of_h
+                .keys()
+                .eq(previous.neg_powers_of_h.keys()),
+        "The next parameters have a different size than the previous ones"
+    );
+    let g = generator_g1(previous)?;
+    ensure!(
+        generator_g1(next)? == g && next.h == previous.h,
+        "The contribution changed the generators"
+    );
+
+    let tau_g1: G1Affine = from_hex(&contribution.tau_g1, "tau_g1")?;
+    let tau_g2: G2Affine = from_hex(&contribution.tau_g2, "tau_g2")?;
+    let proof_commitment: G1Affine = from_hex(&contribution.proof_commitment, "proof commitment")?;
+    let proof_response: Fr = from_hex(&contribution.proof_response, "proof response")?;
+    ensure!(!tau_g1.is_zero(), "The contribution secret is zero");
+    ensure!(
+        Bls12_381::pairing(tau_g1, previous.h) == Bls12_381::pairing(g, tau_g2),
+        "The contribution secret differs between G1 and G2"
+    );
+    let challenge = proof_challenge(&contribution.previous_checksum, &tau_g1, &proof_commitment)?;
+    ensure!(
+        g.mul(proof_response.into_repr())
+            == proof_commitment.into_projective() + tau_g1.mul(challenge.into_repr()),
+        "Invalid proof of knowledge of the contribution secret"
+    );
+
+    // β·g of the next parameters has to be τ times the previous one.
+    let previous_beta_g = power_of_g(previous, 1)?;
+    let next_beta_g = power_of_g(next, 1)?;
+    ensure!(
+        Bls12_381::pairing(next_beta_g, next.h) == Bls12_381::pairing(previous_beta_g, tau_g2)
+            && Bls12_381::pairing(next_beta_g, next.h) == Bls12_381::pairing(g, next.beta_h),
+        "The contribution was not applied to β"
+    );
+
+    let rng = &mut OsRng;
+    let powers_of_gamma_g: Vec<G1Affine> = next.powers_of_gamma_g.values().copied().collect();
+    let neg_powers_of_h: Vec<G2Affine> = next.neg_powers_of_h.values().copied().collect();
+    ensure!(
+        are_consecutive_keys(&next.powers_of_gamma_g)
+            && are_consecutive_keys(&next.neg_powers_of_h),
+        "The next parameters are missing powers"
+    );
+    ensure!(
+        are_consecutive_powers_g1(&next.powers_of_g, next, rng)
+            && are_consecutive_powers_g1(&powers_of_gamma_g, next, rng),
+        "The powers of β in G1 are not consecutive"
+    );
+    ensure!(
+        neg_powers_of_h.first().map_or(true, |h| *h == next.h)
+            && are_consecutive_neg_powers_g2(&neg_powers_of_h, g, next_beta_g, rng),
+        "The negative powers of β in G2 are not consecutive"
+    );
+    Ok(())
+}
+
+/// Contributes to the parameters in the `input` file, writes the new ones
+/// to `output` and appends the contribution to the transcript. The input
+/// has to be the result of the last contribution in the transcript, if any.
+pub fn contribute_to_file(input: &Path, output: &Path, transcript_path: &Path) -> Result<()> {
+    let header = universal_srs::read_universal_srs_header(input)?;
+    let mut transcript = Transcript::read_from_file(transcript_path)?;
+    if let Some(last_contribution) = transcript.contributions.last() {
+        ensure!(
+            last_contribution.checksum == hex::encode(header.checksum),
+            "{} is not the result of the last contribution in the transcript",
+            input.display()
+        );
+    }
+
+    let previous = universal_srs::load_universal_srs_from_path(input, LoadMode::Checked)?;
+    let (next, contribution) = contribute(&previous, &mut OsRng)?;
+    universal_srs::write_universal_srs_to_path(&next, &header.bounds, output)?;
+    transcript.contributions.push(contribution);
+    transcript.write_to_file(transcript_path)
+}
+
+/// Verifies that each parameters file is the result of a contribution in the
+/// transcript on top of the file before it. Returns the positions of those
+/// contributions in the transcript.
+pub fn verify_contribution_files(
+    transcript_path: &Path,
+    parameters_paths: &[&Path],
+) -> Result<Vec<usize>> {
+    ensure!(
+        parameters_paths.len() >= 2,
+        "At least two parameters files are needed to verify a contribution"
+    );
+    let transcript = Transcript::read_from_file(transcript_path)?;
+
+    let mut positions = Vec::new();
+    let mut paths = parameters_paths.iter();
+    let mut previous_path = paths
+        .next()
+        .ok_or_else(|| anyhow!("Missing parameters file"))?;
+    let mut previous =
+        universal_srs::load_universal_srs_from_path(previous_path, LoadMode::Checked)?;
+    for next_path in paths {
+        let next = universal_srs::load_universal_srs_from_path(next_path, LoadMode::Checked)?;
+        let previous_checksum = hex::encode(universal_srs::universal_srs_checksum(&previous)?);
+        let checksum = hex::encode(universal_srs::universal_srs_checksum(&next)?);
+        let (position, contribution) =
+            transcript
+                .find(&previous_checksum, &checksum)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "No contribution in the transcript turns {} into {}",
+                        previous_path.display(),
+                        next_path.display()
+                    )
+                })?;
+        verify_contribution(&previous, &next, contribution).map_err(|e| {
+            anyhow!(
+                "Invalid contribution from {} to {}: {e}",
+                previous_path.display(),
+                next_path.display()
+            )
+        })?;
+        positions.push(position);
+        previous = next;
+        previous_path = next_path;
+    }
+    Ok(positions)
+}
+
+fn nonzero_scalar<R: RngCore + CryptoRng>(rng: &mut R) -> Fr {
+    loop {
+        let scalar = Fr::rand(rng);
+        if !scalar.is_zero() {
+            return scalar;
+        }
+    }
+}
+
+fn power_of_g(universal_srs: &UniversalSRS, power: usize) -> Result<G1Affine> {
+    universal_srs
+        .powers_of_g
+        .get(power)
+        .copied()
+        .ok_or_else(|| anyhow!("The parameters are missing powers of β"))
+}
+
+fn generator_g1(universal_srs: &UniversalSRS) -> Result<G1Affine> {
+    power_of_g(universal_srs, 0)
+}
+
+fn normalize_map<G: ProjectiveCurve>(powers: Vec<(usize, G)>) -> BTreeMap<usize, G::Affine> {
+    let (keys, values): (Vec<usize>, Vec<G>) = powers.into_iter().unzip();
+    keys.into_iter()
+        .zip(G::batch_normalization_into_affine(&values))
+        .collect()
+}
+
+fn are_consecutive_keys<T>(map: &BTreeMap<usize, T>) -> bool {
+    map.keys().copied().eq(0..map.len())
+}
+
+fn random_coefficients<R: RngCore + CryptoRng>(
+    len: usize,
+    rng: &mut R,
+) -> Vec<<Fr as PrimeField>::BigInt> {
+    (0..len).map(|_| Fr::rand(rng).into_repr()).collect()
+}
+
+/// Checks that each element is the previous one times β, that is
+/// e(pᵢ₊₁, h) = e(pᵢ, β·h), for all of them at once by combining them with
+/// random coefficients.
+fn are_consecutive_powers_g1<R: RngCore + CryptoRng>(
+    powers: &[G1Affine],
+    universal_srs: &UniversalSRS,
+    rng: &mut R,
+) -> bool {
+    let (current, next) = match (powers.split_last(), powers.get(1..)) {
+        (Some((_, current)), Some(next)) => (current, next),
+        _ => return true,
+    };
+    let coefficients = random_coefficients(current.len(), rng);
+    let current_sum = VariableBaseMSM::multi_scalar_mul(current, &coefficients);
+    let next_sum = VariableBaseMSM::multi_scalar_mul(next, &coefficients);
+    Bls12_381::pairing(next_sum, universal_srs.h)
+        == Bls12_381::pairing(current_sum, universal_srs.beta_h)
+}
+
+/// Same as `are_consecutive_powers_g1` for h/βⁱ, where each element times β
+/// is the previous one: e(β·g, hᵢ₊₁) = e(g, hᵢ).
+fn are_consecutive_neg_powers_g2<R: RngCore + CryptoRng>(
+    neg_powers: &[G2Affine],
+    g: G1Affine,
+    beta_g: G1Affine,
+    rng: &mut R,
+) -> bool {
+    let (current, next) = match (neg_powers.split_last(), neg_powers.get(1..)) {
+        (Some((_, current)), Some(next)) => (current, next),
+        _ => return true,
+    };
+    let coefficients = random_coefficients(current.len(), rng);
+    let current_sum = VariableBaseMSM::multi_scalar_mul(current, &coefficients);
+    let next_sum = VariableBaseMSM::multi_scalar_mul(next, &coefficients);
+    Bls12_381::pairing(beta_g, next_sum) == Bls12_381::pairing(g, current_sum)
+}
+
+/// Fiat-Shamir challenge of the proof of knowledge.
+fn proof_challenge(
+    previous_checksum: &str,
+    tau_g1: &G1Affine,
+    proof_commitment: &G1Affine,
+) -> Result<Fr> {
+    let mut hasher = Sha256::new();
+    hasher.update(previous_checksum.as_bytes());
+    hasher.update(to_bytes(tau_g1)?);
+    hasher.update(to_bytes(proof_commitment)?);
+    Ok(Fr::from_le_bytes_mod_order(&hasher.finalize()))
+}
+
+fn to_bytes(value: &impl CanonicalSerialize) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    value.serialize(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn to_hex(value: &impl CanonicalSerialize) -> Result<String> {
+    Ok(hex::encode(to_bytes(value)?))
+}
+
+fn from_hex<T: CanonicalDeserialize>(value: &str, name: &str) -> Result<T> {
+    let bytes = hex::decode(value).map_err(|e| anyhow!("Invalid {name}: {e}"))?;
+    T::deserialize(bytes.as_slice()).map_err(|e| anyhow!("Invalid {name}: {e}"))
+}
+
+#[cfg(test)]
+mod ceremony_tests {
+    use super::{contribute, verify_contribution};
+    use crate::universal_srs::{self, UniversalSRSBounds};
+    use rand::rngs::OsRng;
+
+    const SMALL_BOUNDS: UniversalSRSBounds = UniversalSRSBounds {
+        num_constraints: 100,
+        num_variables: 25,
+        num_non_zero: 300,
+    };
+
+    #[test]
+    fn test_contributions_verify() {
+        let initial = universal_srs::generate_universal_srs_with_bounds(&SMALL_BOUNDS).unwrap();
+        let (first, first_contribution) = contribute(&initial, &mut OsRng).unwrap();
+        let (second, second_contribution) = contribute(&first, &mut OsRng).unwrap();
+
+        assert!(verify_contribution(&initial, &first, &first_contribution).is_ok());
+        assert!(verify_contribution(&first, &second, &second_contribution).is_ok());
+        assert_ne!(first_contribution.checksum, second_contribution.checksum);
+    }
+
+    #[test]
+    fn test_contribution_does_not_verify_for_other_parameters() {
+        let initial = universal_srs::generate_universal_srs_with_bounds(&SMALL_BOUNDS).unwrap();
+        let (first, first_contribution) = contribute(&initial, &mut OsRng).unwrap();
+        let (second, _second_contribution) = contribute(&first, &mut OsRng).unwrap();
+
+        assert!(verify_contribution(&initial, &second, &first_contribution).is_err());
+        assert!(verify_contribution(&first, &second, &first_contribution).is_err());
+    }
+
+    #[test]
+    fn test_tampered_contribution_does_not_verify() {
+        let initial = universal_srs::generate_universal_srs_with_bounds(&SMALL_BOUNDS).unwrap();
+        let (first, mut first_contribution) = contribute(&initial, &mut OsRng).unwrap();
+        let (_second, second_contribution) = contribute(&first, &mut OsRng).unwrap();
+
+        // A proof of knowledge made for other parameters can't be reused.
+        first_contribution.proof_commitment = second_contribution.proof_commitment;
+        first_contribution.proof_response = second_contribution.proof_response;
+        assert!(verify_contribution(&initial, &first, &first_contribution).is_err());
+    }
+}
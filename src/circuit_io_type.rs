@@ -1,16 +1,19 @@
-use crate::record::Record;
+use crate::{record::Record, StringGadget};
 use anyhow::Result;
 use ark_r1cs_std::{prelude::Boolean, R1CSVar};
+use indexmap::IndexMap;
 use simpleworks::gadgets::{
     traits::IsWitness, AddressGadget, ConstraintF, FieldGadget, Int8Gadget, UInt16Gadget,
     UInt32Gadget, UInt64Gadget, UInt8Gadget,
 };
 
 pub use CircuitIOType::{
-    SimpleAddress, SimpleBoolean, SimpleField, SimpleInt8, SimpleRecord, SimpleUInt16,
-    SimpleUInt32, SimpleUInt64, SimpleUInt8,
+    SimpleAddress, SimpleBoolean, SimpleField, SimpleInt8, SimpleRecord, SimpleString,
+    SimpleStruct, SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8,
 };
 
+pub type VMStructMembersMap = IndexMap<String, CircuitIOType>;
+
 #[derive(Clone, Debug)]
 pub enum CircuitIOType {
     SimpleUInt8(UInt8Gadget),
@@ -22,6 +25,8 @@ pub enum CircuitIOType {
     SimpleAddress(AddressGadget),
     SimpleBoolean(Boolean<ConstraintF>),
     SimpleField(FieldGadget),
+    SimpleStruct(VMStructMembersMap),
+    SimpleString(StringGadget),
 }
 
 impl CircuitIOType {
@@ -41,6 +46,14 @@ impl CircuitIOType {
             SimpleAddress(value) => Ok(value.value()?),
             SimpleBoolean(value) => Ok(value.value()?.to_string()),
             CircuitIOType::SimpleField(value) => Ok(value.value()?.to_string()),
+            SimpleStruct(members) => {
+                let mut formatted_members = Vec::with_capacity(members.len());
+                for (name, member) in members {
+                    formatted_members.push(format!("{name}: {}", member.value()?));
+                }
+                Ok(format!("{{ {} }}", formatted_members.join(", ")))
+            }
+            SimpleString(value) => Ok(value.value()?),
         }
     }
 
@@ -58,6 +71,16 @@ impl CircuitIOType {
             SimpleAddress(v) => v.is_witness(),
             SimpleBoolean(v) => v.is_witness(),
             SimpleField(v) => v.is_witness(),
+            // A struct is a witness if any of its members is.
+            SimpleStruct(members) => {
+                for member in members.values() {
+                    if member.is_witness()? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            SimpleString(v) => v.is_witness(),
         }
     }
 
@@ -75,6 +98,8 @@ impl CircuitIOType {
             SimpleAddress(v) => v.is_constant(),
             SimpleBoolean(v) => v.is_constant(),
             SimpleField(v) => v.is_constant(),
+            SimpleStruct(members) => members.values().all(CircuitIOType::is_constant),
+            SimpleString(v) => v.is_constant(),
         }
     }
 }
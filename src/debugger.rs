@@ -0,0 +1,118 @@
+use crate::{
+    helpers,
+    jaleo::{Identifier, Program, UserInputValueType},
+    SimpleFunctionVariables,
+};
+use anyhow::{anyhow, Result};
+use ark_relations::r1cs::ConstraintSystem;
+use simpleworks::{gadgets::ConstraintF, marlin::ConstraintSystemRef};
+use snarkvm::prelude::{Function, Instruction, Testnet3};
+
+/// What happened when an instruction was processed.
+#[derive(Clone, Debug)]
+pub struct Step {
+    /// Index of the instruction in the function.
+    pub index: usize,
+    pub instruction: String,
+    /// The register the instruction wrote to, if any (asserts have none).
+    pub destination: Option<String>,
+    /// Number of constraints the instruction added to the constraint system.
+    pub constraints: usize,
+    /// Whether the constraint system is still satisfied after the instruction.
+    pub satisfied: bool,
+}
+
+/// Synthesizes a function one instruction at a time, so the registers and
+/// the constraint system can be inspected in between.
+pub struct Debugger<'program> {
+    program: &'program Program,
+    function: Function<Testnet3>,
+    constraint_system: ConstraintSystemRef,
+    function_variables: SimpleFunctionVariables,
+    next_instruction: usize,
+}
+
+impl<'program> Debugger<'program> {
+    /// Creates a debugger with the inputs of the function already processed,
+    /// stopped before its first instruction.
+    pub fn new(
+        program: &'program Program,
+        imports: &[Program],
+        function_name: &str,
+        user_inputs: &[UserInputValueType],
+    ) -> Result<Self> {
+        let function = program.get_function(&Identifier::try_from(function_name)?)?;
+        helpers::check_user_inputs(program, imports, &function, user_inputs)?;
+        let constraint_system = ConstraintSystem::<ConstraintF>::new_ref();
+        let mut function_variables =
+            helpers::function_variables(&function, constraint_system.clone())?;
+        helpers::process_inputs(
+            program,
+            imports,
+            &function,
+            &constraint_system,
+            user_inputs,
+            &mut function_variables,
+        )?;
+
+        Ok(Self {
+            program,
+            function,
+            constraint_system,
+            function_variables,
+            next_instruction: 0,
+        })
+    }
+
+    pub fn instructions(&self) -> &[Instruction<Testnet3>] {
+        self.function.instructions()
+    }
+
+    /// Index of the instruction the next step processes.
+    pub fn next_instruction(&self) -> usize {
+        self.next_instruction
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_instruction >= self.instructions().len()
+    }
+
+    pub fn registers(&self) -> &SimpleFunctionVariables {
+        &self.function_variables
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.constraint_system.num_constraints()
+    }
+
+    pub fn is_satisfied(&self) -> Result<bool> {
+        Ok(self.constraint_system.is_satisfied()?)
+    }
+
+    /// Processes the next instruction, returns `None` if there are no more.
+    pub fn step(&mut self) -> Result<Option<Step>> {
+        let index = self.next_instruction;
+        let instruction = match self.function.instructions().get(index) {
+            Some(instruction) => instruction.clone(),
+            None => return Ok(None),
+        };
+
+        let constraints_before = self.num_constraints();
+        let destination = helpers::process_instruction(
+            self.program,
+            &instruction,
+            &mut self.function_variables,
+            self.constraint_system.clone(),
+        )
+        .map_err(|e| anyhow!("Instruction {index} ({instruction}) failed: {e}"))?;
+        self.next_instruction += 1;
+
+        Ok(Some(Step {
+            index,
+            instruction: instruction.to_string(),
+            destination,
+            constraints: self.num_constraints() - constraints_before,
+            satisfied: self.is_satisfied()?,
+        }))
+    }
+}
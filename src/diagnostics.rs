@@ -0,0 +1,441 @@
+use crate::jaleo::Program;
+use indexmap::IndexSet;
+use serde::Serialize;
+use snarkvm::prelude::{
+    EntryType, Function, Instruction, Literal, LiteralType, Operand, PlaintextType, RegisterType,
+    Testnet3, ValueType,
+};
+use std::fmt::Display;
+
+/// Something in a program that cannot be synthesized yet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// Where in the program it is, e.g. `function transfer, input r0`.
+    pub location: String,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// Walks the whole program and reports every unsupported instruction, type,
+/// operand and visibility combination, so users find out before generating
+/// any keys. An empty result means the program only uses supported features.
+pub fn check_program(program: &Program) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (interface_name, interface) in program.interfaces() {
+        for (member_name, member_type) in interface.members() {
+            check_plaintext_type(
+                member_type,
+                &format!("interface {interface_name}, member {member_name}"),
+                &mut diagnostics,
+            );
+        }
+    }
+
+    for (record_name, record) in program.records() {
+        for (entry_name, entry_type) in record.entries() {
+            let (EntryType::Constant(plaintext_type)
+            | EntryType::Public(plaintext_type)
+            | EntryType::Private(plaintext_type)) = entry_type;
+            check_plaintext_type(
+                plaintext_type,
+                &format!("record {record_name}, entry {entry_name}"),
+                &mut diagnostics,
+            );
+        }
+    }
+
+    for (closure_name, closure) in program.closures() {
+        diagnostics.push(Diagnostic {
+            location: format!("closure {closure_name}"),
+            message: "closures are not supported".to_owned(),
+        });
+        for (index, instruction) in closure.instructions().iter().enumerate() {
+            check_instruction(
+                instruction,
+                &format!("closure {closure_name}, instruction {index} ({instruction})"),
+                &mut diagnostics,
+            );
+        }
+    }
+
+    for (function_name, function) in program.functions() {
+        for input in function.inputs() {
+            check_value_type(
+                input.value_type(),
+                &format!("function {function_name}, input {}", input.register()),
+                &mut diagnostics,
+            );
+        }
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            check_instruction(
+                instruction,
+                &format!("function {function_name}, instruction {index} ({instruction})"),
+                &mut diagnostics,
+            );
+        }
+        for output in function.outputs() {
+            check_value_type(
+                output.value_type(),
+                &format!("function {function_name}, output {}", output.register()),
+                &mut diagnostics,
+            );
+        }
+        check_constants(program, function, &mut diagnostics);
+    }
+
+    diagnostics
+}
+
+fn literal_type_is_supported(literal_type: &LiteralType) -> bool {
+    matches!(
+        literal_type,
+        LiteralType::Address
+            | LiteralType::Boolean
+            | LiteralType::Field
+            | LiteralType::I8
+            | LiteralType::String
+            | LiteralType::U8
+            | LiteralType::U16
+            | LiteralType::U32
+            | LiteralType::U64
+    )
+}
+
+fn check_plaintext_type(
+    plaintext_type: &PlaintextType<Testnet3>,
+    location: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    // Interfaces are checked on their own, member by member.
+    if let PlaintextType::Literal(literal_type) = plaintext_type {
+        if !literal_type_is_supported(literal_type) {
+            diagnostics.push(Diagnostic {
+                location: location.to_owned(),
+                message: format!("type {literal_type} is not supported"),
+            });
+        }
+    }
+}
+
+fn check_value_type(
+    value_type: &ValueType<Testnet3>,
+    location: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match value_type {
+        ValueType::Constant(plaintext_type)
+        | ValueType::Public(plaintext_type)
+        | ValueType::Private(plaintext_type) => {
+            check_plaintext_type(plaintext_type, location, diagnostics);
+        }
+        ValueType::Record(_) | ValueType::ExternalRecord(_) => {}
+    }
+}
+
+/// Constant outputs and constant record entries are part of the circuit, so
+/// like `helpers::process_outputs` and `instructions::cast` they can only
+/// come from constant inputs and literals.
+fn check_constants(
+    program: &Program,
+    function: &Function<Testnet3>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let function_name = function.name();
+    // Locators of the registers whose value is not known when the circuit
+    // is built.
+    let mut non_constant_registers = IndexSet::new();
+    for input in function.inputs() {
+        if !matches!(input.value_type(), ValueType::Constant(_)) {
+            non_constant_registers.insert(input.register().locator());
+        }
+    }
+    let is_non_constant = |operand: &Operand<Testnet3>, registers: &IndexSet<u64>| matches!(operand, Operand::Register(register) if registers.contains(&register.locator()));
+
+    for (index, instruction) in function.instructions().iter().enumerate() {
+        if let Instruction::Cast(cast) = instruction {
+            if let RegisterType::Record(record_name) = cast.register_type() {
+                if let Ok(record) = program.get_record(record_name) {
+                    // The first two operands are the owner and the gates.
+                    for ((entry_name, entry_type), operand) in record
+                        .entries()
+                        .iter()
+                        .zip(instruction.operands().iter().skip(2))
+                    {
+                        if matches!(entry_type, EntryType::Constant(_))
+                            && is_non_constant(operand, &non_constant_registers)
+                        {
+                            diagnostics.push(Diagnostic {
+                                location: format!(
+                                    "function {function_name}, instruction {index} ({instruction})"
+                                ),
+                                message: format!(
+                                    "entry {entry_name} is constant but its value is not a constant"
+                                ),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        let depends_on_non_constants = instruction
+            .operands()
+            .iter()
+            .any(|operand| is_non_constant(operand, &non_constant_registers));
+        if depends_on_non_constants {
+            for destination in instruction.destinations() {
+                non_constant_registers.insert(destination.locator());
+            }
+        }
+    }
+
+    for output in function.outputs() {
+        if matches!(output.value_type(), ValueType::Constant(_))
+            && non_constant_registers.contains(&output.register().locator())
+        {
+            diagnostics.push(Diagnostic {
+                location: format!("function {function_name}, output {}", output.register()),
+                message: "output is declared as constant but its value is not a constant"
+                    .to_owned(),
+            });
+        }
+    }
+}
+
+fn check_instruction(
+    instruction: &Instruction<Testnet3>,
+    location: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    // Keep in sync with helpers::process_instruction.
+    let opcode_is_supported = matches!(
+        instruction,
+        Instruction::Add(_)
+            | Instruction::AssertEq(_)
+            | Instruction::AssertNeq(_)
+            | Instruction::And(_)
+            | Instruction::Cast(_)
+            | Instruction::Div(_)
+            | Instruction::GreaterThan(_)
+            | Instruction::GreaterThanOrEqual(_)
+            | Instruction::HashPSD2(_)
+            | Instruction::IsEq(_)
+            | Instruction::IsNeq(_)
+            | Instruction::LessThan(_)
+            | Instruction::LessThanOrEqual(_)
+            | Instruction::Nand(_)
+            | Instruction::Or(_)
+            | Instruction::Xor(_)
+            | Instruction::Nor(_)
+            | Instruction::Mul(_)
+            | Instruction::Shl(_)
+            | Instruction::Shr(_)
+            | Instruction::Sub(_)
+            | Instruction::Ternary(_)
+    );
+    if !opcode_is_supported {
+        diagnostics.push(Diagnostic {
+            location: location.to_owned(),
+            message: format!("{} instruction is not supported", instruction.opcode()),
+        });
+    }
+
+    if let Instruction::Cast(cast) = instruction {
+        match cast.register_type() {
+            RegisterType::Record(_) | RegisterType::Plaintext(PlaintextType::Interface(_)) => {}
+            RegisterType::Plaintext(plaintext_type) => {
+                check_plaintext_type(plaintext_type, location, diagnostics);
+            }
+            RegisterType::ExternalRecord(locator) => diagnostics.push(Diagnostic {
+                location: location.to_owned(),
+                message: format!("casting into external record {locator} is not supported"),
+            }),
+        }
+    }
+
+    for operand in instruction.operands() {
+        let unsupported_operand = match operand {
+            Operand::Register(_)
+            | Operand::Literal(
+                Literal::Address(_)
+                | Literal::Boolean(_)
+                | Literal::I8(_)
+                | Literal::String(_)
+                | Literal::U8(_)
+                | Literal::U16(_)
+                | Literal::U32(_)
+                | Literal::U64(_),
+            ) => None,
+            Operand::Literal(literal) => Some(format!(
+                "{} literal operands are not supported",
+                literal.to_type()
+            )),
+            Operand::ProgramID(_) => Some("program ID operands are not supported".to_owned()),
+            Operand::Caller => Some("caller operands are not supported".to_owned()),
+        };
+        if let Some(message) = unsupported_operand {
+            diagnostics.push(Diagnostic {
+                location: location.to_owned(),
+                message,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod diagnostics_tests {
+    use super::{check_program, Diagnostic};
+    use crate::jaleo::Program;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_supported_program_has_no_diagnostics() {
+        let program = Program::from_str(
+            "program main.aleo;
+
+interface point:
+    x as u64;
+    y as u64;
+
+record token:
+    owner as address.private;
+    gates as u64.private;
+    amount as u64.private;
+
+function hello_1:
+    input r0 as point.public;
+    input r1 as token.record;
+    add r0.x r1.amount into r2;
+    add r2 1u64 into r3;
+    output r3 as u64.private;",
+        )
+        .unwrap();
+
+        assert!(check_program(&program).is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_features_are_reported() {
+        let program = Program::from_str(
+            "program main.aleo;
+
+interface point:
+    x as u128;
+    y as u64;
+
+closure double:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function hello_1:
+    input r0 as i16.public;
+    input r1 as u64.private;
+    pow r1 2u8 into r2;
+    add r1 1field into r3;
+    output r2 as u64.private;",
+        )
+        .unwrap();
+
+        assert_eq!(
+            check_program(&program),
+            vec![
+                Diagnostic {
+                    location: "interface point, member x".to_owned(),
+                    message: "type u128 is not supported".to_owned(),
+                },
+                Diagnostic {
+                    location: "closure double".to_owned(),
+                    message: "closures are not supported".to_owned(),
+                },
+                Diagnostic {
+                    location: "function hello_1, input r0".to_owned(),
+                    message: "type i16 is not supported".to_owned(),
+                },
+                Diagnostic {
+                    location: "function hello_1, instruction 0 (pow r1 2u8 into r2;)".to_owned(),
+                    message: "pow instruction is not supported".to_owned(),
+                },
+                Diagnostic {
+                    location: "function hello_1, instruction 1 (add r1 1field into r3;)".to_owned(),
+                    message: "field literal operands are not supported".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_and_struct_record_entries_are_supported() {
+        let program = Program::from_str(
+            "program main.aleo;
+
+interface point:
+    x as u64;
+    y as u64;
+
+record ticket:
+    owner as address.private;
+    gates as u64.private;
+    name as string.public;
+    location as point.private;
+
+function hello_1:
+    input r0 as address.private;
+    input r1 as string.public;
+    input r2 as point.private;
+    cast r0 0u64 r1 r2 into r3 as ticket.record;
+    output r3 as ticket.record;",
+        )
+        .unwrap();
+
+        assert!(check_program(&program).is_empty());
+    }
+
+    #[test]
+    fn test_non_constant_values_in_constants_are_reported() {
+        let program = Program::from_str(
+            "program main.aleo;
+
+record ticket:
+    owner as address.private;
+    gates as u64.private;
+    amount as u64.constant;
+
+function hello_1:
+    input r0 as u64.constant;
+    input r1 as u64.private;
+    input r2 as address.private;
+    add r0 1u64 into r3;
+    add r0 r1 into r4;
+    cast r2 0u64 r3 into r5 as ticket.record;
+    cast r2 0u64 r4 into r6 as ticket.record;
+    output r3 as u64.constant;
+    output r4 as u64.constant;
+    output r5 as ticket.record;
+    output r6 as ticket.record;",
+        )
+        .unwrap();
+
+        assert_eq!(
+            check_program(&program),
+            vec![
+                Diagnostic {
+                    location: "function hello_1, instruction 3 (cast r2 0u64 r4 into r6 as ticket.record;)"
+                        .to_owned(),
+                    message: "entry amount is constant but its value is not a constant".to_owned(),
+                },
+                Diagnostic {
+                    location: "function hello_1, output r4".to_owned(),
+                    message: "output is declared as constant but its value is not a constant"
+                        .to_owned(),
+                },
+            ]
+        );
+    }
+}
@@ -1,15 +1,18 @@
 use crate::{
     circuit_io_type::{
-        SimpleAddress, SimpleBoolean, SimpleField, SimpleInt8, SimpleRecord, SimpleUInt16,
-        SimpleUInt32, SimpleUInt64, SimpleUInt8,
+        SimpleAddress, SimpleBoolean, SimpleField, SimpleInt8, SimpleRecord, SimpleString,
+        SimpleStruct, SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8, VMStructMembersMap,
     },
     instructions::{self},
-    jaleo::{Identifier, Program, Record as JAleoRecord, RecordEntriesMap, UserInputValueType},
+    jaleo::{
+        Identifier, Program, Record as JAleoRecord, RecordEntriesMap, RecordEntry,
+        StructMembersMap, UserInputValueType, Visibility,
+    },
     record::Record as VMRecord,
-    CircuitIOType, SimpleFunctionVariables,
+    CircuitIOType, SimpleFunctionVariables, StringGadget,
 };
-use anyhow::{anyhow, bail, Result};
-use ark_r1cs_std::prelude::{AllocVar, Boolean};
+use anyhow::{anyhow, bail, ensure, Result};
+use ark_r1cs_std::prelude::{AllocVar, AllocationMode, Boolean};
 use ark_relations::r1cs::Namespace;
 use indexmap::IndexMap;
 use simpleworks::{
@@ -20,7 +23,7 @@ use simpleworks::{
     marlin::ConstraintSystemRef,
 };
 use snarkvm::prelude::{
-    EntryType, Function, Group, Instruction, Literal, LiteralType, Operand, PlaintextType,
+    EntryType, Function, Group, Instruction, Literal, LiteralType, Locator, Operand, PlaintextType,
     Register, Scalar, Testnet3, Uniform, ValueType,
 };
 
@@ -53,6 +56,7 @@ pub fn bytes_to_string(bytes: &[u8]) -> Result<String> {
 /// Defaults the inputs for a given function.
 pub(crate) fn default_user_inputs(
     program: &Program,
+    imports: &[Program],
     function_name: &Identifier,
 ) -> Result<Vec<UserInputValueType>> {
     let mut default_user_inputs: Vec<UserInputValueType> = Vec::new();
@@ -101,6 +105,16 @@ pub(crate) fn default_user_inputs(
             | ValueType::Private(PlaintextType::Literal(LiteralType::Boolean)) => {
                 UserInputValueType::Boolean(false)
             }
+            // String
+            ValueType::Public(PlaintextType::Literal(LiteralType::String))
+            | ValueType::Private(PlaintextType::Literal(LiteralType::String)) => {
+                UserInputValueType::String(String::default())
+            }
+            // Structs
+            ValueType::Public(PlaintextType::Interface(interface_identifier))
+            | ValueType::Private(PlaintextType::Interface(interface_identifier)) => {
+                UserInputValueType::Struct(default_struct_members(program, interface_identifier)?)
+            }
             // Unsupported Cases
             ValueType::Public(v) | ValueType::Private(v) => {
                 println!("UNSUPPORTED TYPE: {v:?}");
@@ -113,14 +127,28 @@ pub(crate) fn default_user_inputs(
                 UserInputValueType::Record(JAleoRecord {
                     owner: *b"aleo11111111111111111111111111111111111111111111111111111111111",
                     gates: u64::default(),
-                    data: aleo_entries_to_vm_entries(aleo_record_entries)?,
+                    data: aleo_entries_to_vm_entries(program, aleo_record_entries)?,
                     nonce: Some(random_nonce()),
                 })
             }
             // Constant Types
-            ValueType::Constant(_) => bail!("Constant types are not supported"),
+            // Constants are part of the circuit so the keys built with these
+            // defaults only verify proofs that used the same constants.
+            ValueType::Constant(plaintext_type) => {
+                default_plaintext_value(program, plaintext_type)?
+            }
             // External Records
-            ValueType::ExternalRecord(_) => bail!("ExternalRecord types are not supported"),
+            ValueType::ExternalRecord(locator) => {
+                let external_program = external_program(program, imports, locator)?;
+                let aleo_record = external_program.get_record(locator.resource())?;
+                let aleo_record_entries = aleo_record.entries();
+                UserInputValueType::Record(JAleoRecord {
+                    owner: *b"aleo11111111111111111111111111111111111111111111111111111111111",
+                    gates: u64::default(),
+                    data: aleo_entries_to_vm_entries(external_program, aleo_record_entries)?,
+                    nonce: Some(random_nonce()),
+                })
+            }
         };
         default_user_inputs.push(default_user_input);
     }
@@ -128,64 +156,273 @@ pub(crate) fn default_user_inputs(
 }
 
 pub fn aleo_entries_to_vm_entries(
+    program: &Program,
     aleo_entries: &IndexMap<Identifier, EntryType<Testnet3>>,
 ) -> Result<RecordEntriesMap> {
     let mut vm_entries = RecordEntriesMap::new();
     for (aleo_entry_identifier, aleo_entry_type) in aleo_entries {
         let vm_entry_type = match aleo_entry_type {
-            EntryType::Constant(PlaintextType::Literal(LiteralType::Address))
-            | EntryType::Public(PlaintextType::Literal(LiteralType::Address))
-            | EntryType::Private(PlaintextType::Literal(LiteralType::Address)) => {
-                UserInputValueType::Address(
-                    *b"aleo11111111111111111111111111111111111111111111111111111111111",
-                )
-            }
-            EntryType::Constant(PlaintextType::Literal(LiteralType::U8))
-            | EntryType::Public(PlaintextType::Literal(LiteralType::U8))
-            | EntryType::Private(PlaintextType::Literal(LiteralType::U8)) => {
-                UserInputValueType::U8(u8::default())
-            }
-            EntryType::Constant(PlaintextType::Literal(LiteralType::U16))
-            | EntryType::Public(PlaintextType::Literal(LiteralType::U16))
-            | EntryType::Private(PlaintextType::Literal(LiteralType::U16)) => {
-                UserInputValueType::U16(u16::default())
-            }
-            EntryType::Constant(PlaintextType::Literal(LiteralType::U32))
-            | EntryType::Public(PlaintextType::Literal(LiteralType::U32))
-            | EntryType::Private(PlaintextType::Literal(LiteralType::U32)) => {
-                UserInputValueType::U32(u32::default())
-            }
-            EntryType::Constant(PlaintextType::Literal(LiteralType::U64))
-            | EntryType::Public(PlaintextType::Literal(LiteralType::U64))
-            | EntryType::Private(PlaintextType::Literal(LiteralType::U64)) => {
-                UserInputValueType::U64(u64::default())
-            }
-            EntryType::Constant(PlaintextType::Literal(LiteralType::U128))
-            | EntryType::Public(PlaintextType::Literal(LiteralType::U128))
-            | EntryType::Private(PlaintextType::Literal(LiteralType::U128)) => {
-                UserInputValueType::U128(u128::default())
-            }
-            EntryType::Constant(PlaintextType::Literal(LiteralType::I8))
-            | EntryType::Public(PlaintextType::Literal(LiteralType::I8))
-            | EntryType::Private(PlaintextType::Literal(LiteralType::I8)) => {
-                UserInputValueType::I8(i8::default())
-            }
-            EntryType::Constant(PlaintextType::Literal(l))
-            | EntryType::Public(PlaintextType::Literal(l))
-            | EntryType::Private(PlaintextType::Literal(l)) => bail!(format!(
-                "Unsupported literal type {l} for entry {aleo_entry_identifier}"
-            )),
-            EntryType::Constant(PlaintextType::Interface(_))
-            | EntryType::Public(PlaintextType::Interface(_))
-            | EntryType::Private(PlaintextType::Interface(_)) => {
-                bail!("Interface type is not supported")
+            EntryType::Constant(plaintext_type)
+            | EntryType::Public(plaintext_type)
+            | EntryType::Private(plaintext_type) => {
+                default_plaintext_value(program, plaintext_type)
+                    .map_err(|e| anyhow!("{e} for entry {aleo_entry_identifier}"))?
             }
         };
-        vm_entries.insert(aleo_entry_identifier.to_string(), vm_entry_type);
+        vm_entries.insert(
+            aleo_entry_identifier.to_string(),
+            RecordEntry::new(vm_entry_type, Visibility::from(aleo_entry_type)),
+        );
     }
     Ok(vm_entries)
 }
 
+/// Defaults the members of the given interface.
+pub fn default_struct_members(
+    program: &Program,
+    interface_identifier: &Identifier,
+) -> Result<StructMembersMap> {
+    let interface = program.get_interface(interface_identifier)?;
+    let mut members = StructMembersMap::new();
+    for (member_identifier, member_type) in interface.members() {
+        members.insert(
+            member_identifier.to_string(),
+            default_plaintext_value(program, member_type)?,
+        );
+    }
+    Ok(members)
+}
+
+fn default_plaintext_value(
+    program: &Program,
+    plaintext_type: &PlaintextType<Testnet3>,
+) -> Result<UserInputValueType> {
+    let default_value = match plaintext_type {
+        PlaintextType::Literal(LiteralType::Address) => UserInputValueType::Address(
+            *b"aleo11111111111111111111111111111111111111111111111111111111111",
+        ),
+        PlaintextType::Literal(LiteralType::U8) => UserInputValueType::U8(u8::default()),
+        PlaintextType::Literal(LiteralType::U16) => UserInputValueType::U16(u16::default()),
+        PlaintextType::Literal(LiteralType::U32) => UserInputValueType::U32(u32::default()),
+        PlaintextType::Literal(LiteralType::U64) => UserInputValueType::U64(u64::default()),
+        PlaintextType::Literal(LiteralType::U128) => UserInputValueType::U128(u128::default()),
+        PlaintextType::Literal(LiteralType::I8) => UserInputValueType::I8(i8::default()),
+        PlaintextType::Literal(LiteralType::Field) => {
+            UserInputValueType::Field(ConstraintF::default())
+        }
+        PlaintextType::Literal(LiteralType::Boolean) => UserInputValueType::Boolean(false),
+        PlaintextType::Literal(LiteralType::String) => {
+            UserInputValueType::String(String::default())
+        }
+        PlaintextType::Literal(l) => bail!("Unsupported literal type {l}"),
+        PlaintextType::Interface(interface_identifier) => {
+            UserInputValueType::Struct(default_struct_members(program, interface_identifier)?)
+        }
+    };
+    Ok(default_value)
+}
+
+/// Checks that the given struct members match the members of the interface,
+/// both in name and type.
+pub fn check_struct_members(
+    program: &Program,
+    interface_identifier: &Identifier,
+    members: &StructMembersMap,
+) -> Result<()> {
+    let interface = program.get_interface(interface_identifier)?;
+    ensure!(
+        interface.members().len() == members.len(),
+        "Interface {interface_identifier} has {} members but {} were given",
+        interface.members().len(),
+        members.len()
+    );
+    for ((member_identifier, member_type), (member_name, member_value)) in
+        interface.members().iter().zip(members)
+    {
+        ensure!(
+            member_identifier.to_string() == *member_name,
+            "Expected member `{member_identifier}` of interface {interface_identifier} but got `{member_name}`"
+        );
+        let member_matches = plaintext_type_matches(program, member_type, member_value)?;
+        ensure!(
+            member_matches,
+            "Mismatched type for member `{member_name}` of interface {interface_identifier}, expected {member_type}"
+        );
+    }
+    Ok(())
+}
+
+/// Returns whether the function takes constant inputs. Constants are part of
+/// the circuit, so such a function has different keys for each constant.
+pub fn function_has_constant_inputs(function: &Function<Testnet3>) -> bool {
+    function
+        .inputs()
+        .iter()
+        .any(|input| matches!(input.value_type(), ValueType::Constant(_)))
+}
+
+/// Returns the program in `imports` that defines the given external record,
+/// which must also be imported by `program`.
+pub fn external_program<'imports>(
+    program: &Program,
+    imports: &'imports [Program],
+    locator: &Locator<Testnet3>,
+) -> Result<&'imports Program> {
+    ensure!(
+        program.imports().contains_key(locator.program_id()),
+        "Program {} does not import {}",
+        program.id(),
+        locator.program_id()
+    );
+    imports
+        .iter()
+        .find(|import| import.id() == locator.program_id())
+        .ok_or_else(|| anyhow!("Imported program {} was not provided", locator.program_id()))
+}
+
+/// Returns the entries declared by the record type of a function input or
+/// output, which is defined in an imported program for external records.
+pub(crate) fn record_entry_types(
+    program: &Program,
+    imports: &[Program],
+    value_type: &ValueType<Testnet3>,
+) -> Result<IndexMap<Identifier, EntryType<Testnet3>>> {
+    let record_type = match value_type {
+        ValueType::Record(record_identifier) => program.get_record(record_identifier)?,
+        ValueType::ExternalRecord(locator) => {
+            external_program(program, imports, locator)?.get_record(locator.resource())?
+        }
+        _ => bail!("{value_type} is not a record type"),
+    };
+    Ok(record_type.entries().clone())
+}
+
+/// Checks that the given record entries match the entries of the record
+/// definition of the given program, in name, type and visibility.
+pub fn check_record_entries(
+    program: &Program,
+    aleo_entries: &IndexMap<Identifier, EntryType<Testnet3>>,
+    entries: &RecordEntriesMap,
+) -> Result<()> {
+    ensure!(
+        aleo_entries.len() == entries.len(),
+        "Record has {} entries but {} were given",
+        aleo_entries.len(),
+        entries.len()
+    );
+    for ((aleo_entry_identifier, aleo_entry_type), (entry_name, entry_value)) in
+        aleo_entries.iter().zip(entries)
+    {
+        ensure!(
+            aleo_entry_identifier.to_string() == *entry_name,
+            "Expected record entry `{aleo_entry_identifier}` but got `{entry_name}`"
+        );
+        let entry_type = match aleo_entry_type {
+            EntryType::Constant(plaintext_type)
+            | EntryType::Public(plaintext_type)
+            | EntryType::Private(plaintext_type) => plaintext_type,
+        };
+        ensure!(
+            plaintext_type_matches(program, entry_type, &entry_value.value)?,
+            "Mismatched type for record entry `{entry_name}`, expected {entry_type}"
+        );
+        let visibility = Visibility::from(aleo_entry_type);
+        ensure!(
+            entry_value.visibility == visibility,
+            "Mismatched visibility for record entry `{entry_name}`, expected {visibility}"
+        );
+    }
+    Ok(())
+}
+
+/// Returns whether the given value is of the given plaintext type. Struct
+/// values are checked member by member.
+pub fn plaintext_type_matches(
+    program: &Program,
+    plaintext_type: &PlaintextType<Testnet3>,
+    value: &UserInputValueType,
+) -> Result<bool> {
+    let matches = match (plaintext_type, value) {
+        (PlaintextType::Literal(LiteralType::Address), UserInputValueType::Address(_))
+        | (PlaintextType::Literal(LiteralType::U8), UserInputValueType::U8(_))
+        | (PlaintextType::Literal(LiteralType::U16), UserInputValueType::U16(_))
+        | (PlaintextType::Literal(LiteralType::U32), UserInputValueType::U32(_))
+        | (PlaintextType::Literal(LiteralType::U64), UserInputValueType::U64(_))
+        | (PlaintextType::Literal(LiteralType::U128), UserInputValueType::U128(_))
+        | (PlaintextType::Literal(LiteralType::I8), UserInputValueType::I8(_))
+        | (PlaintextType::Literal(LiteralType::Field), UserInputValueType::Field(_))
+        | (PlaintextType::Literal(LiteralType::Boolean), UserInputValueType::Boolean(_))
+        | (PlaintextType::Literal(LiteralType::String), UserInputValueType::String(_))
+        | (PlaintextType::Literal(LiteralType::Group), UserInputValueType::Group(_))
+        | (PlaintextType::Literal(LiteralType::Scalar), UserInputValueType::Scalar(_)) => true,
+        (PlaintextType::Interface(interface_identifier), UserInputValueType::Struct(members)) => {
+            check_struct_members(program, interface_identifier, members)?;
+            true
+        }
+        _ => false,
+    };
+    Ok(matches)
+}
+
+/// Checks that the given user inputs match the inputs of the function, so
+/// mistakes are reported with the offending input before any synthesis.
+pub fn check_user_inputs(
+    program: &Program,
+    imports: &[Program],
+    function: &Function<Testnet3>,
+    user_inputs: &[UserInputValueType],
+) -> Result<()> {
+    ensure!(
+        function.inputs().len() == user_inputs.len(),
+        "Function {} expects {} inputs but {} were given",
+        function.name(),
+        function.inputs().len(),
+        user_inputs.len()
+    );
+    for (function_input, user_input) in function.inputs().iter().zip(user_inputs) {
+        let register = function_input.register();
+        let value_type = function_input.value_type();
+        let input_matches = match (value_type, user_input) {
+            (
+                ValueType::Constant(plaintext_type)
+                | ValueType::Public(plaintext_type)
+                | ValueType::Private(plaintext_type),
+                _,
+            ) => plaintext_type_matches(program, plaintext_type, user_input),
+            (ValueType::Record(record_identifier), UserInputValueType::Record(record)) => {
+                check_record_entries(
+                    program,
+                    program.get_record(record_identifier)?.entries(),
+                    &record.data,
+                )
+                .map(|_| true)
+            }
+            (ValueType::ExternalRecord(locator), UserInputValueType::Record(record)) => {
+                let external_program = external_program(program, imports, locator)?;
+                check_record_entries(
+                    external_program,
+                    external_program.get_record(locator.resource())?.entries(),
+                    &record.data,
+                )
+                .map(|_| true)
+            }
+            (ValueType::Record(_) | ValueType::ExternalRecord(_), _) => Ok(false),
+        }
+        .map_err(|e| {
+            anyhow!(
+                "Invalid input {register} of function {}: {e}",
+                function.name()
+            )
+        })?;
+        ensure!(
+            input_matches,
+            "Invalid input {register} of function {}: expected {value_type} but got {user_input}",
+            function.name()
+        );
+    }
+    Ok(())
+}
+
 /// This function builds the scaffold of the program variables.
 /// We use a hash map for such variables, where the key is the variable name
 /// and the value is the variable type.
@@ -290,6 +527,14 @@ pub fn function_variables(
                         **v,
                     )?)),
                 );
+            } else if let Operand::Literal(Literal::String(v)) = o {
+                registers.insert(
+                    o.to_string(),
+                    Some(SimpleString(StringGadget::new_constant(
+                        constraint_system.clone(),
+                        (**v).to_owned(),
+                    )?)),
+                );
             // TODO: Turn a snarkvm_fields::fp_256::Fp256 into an ark_ff::Fp256.
             // } else if let Operand::Literal(Literal::Field(v)) = o {
             //     registers.insert(
@@ -321,6 +566,8 @@ pub fn function_variables(
 /// Instantiates the inputs inside the given constraint system.
 ///
 /// # Parameters
+/// - `program` - program the function belongs to.
+/// - `imports` - programs imported by `program`, needed for external records.
 /// - `function` - function to be analyzed.
 /// - `cs` - Constraint System.
 /// - `user_inputs` - user inputs of the function.
@@ -332,10 +579,10 @@ pub fn function_variables(
 /// # Errors
 /// Literal 'Mismatched function input type with user input type' when function input type differs from user input type.
 /// Literal 'Unsupported type' when user input type is unsupported.
-/// Literal 'Constant types are not supported' when the input is a constant.
-/// Literal 'ExternalRecord types are not supported' when the input is a External Record.
 ///
 pub(crate) fn process_inputs(
+    program: &Program,
+    imports: &[Program],
     function: &Function<Testnet3>,
     cs: &ConstraintSystemRef,
     user_inputs: &[UserInputValueType],
@@ -397,7 +644,7 @@ pub(crate) fn process_inputs(
                 Namespace::new(cs.clone(), None),
                 || Ok(f),
             )?),
-            // Private Boolean
+            // Public Boolean
             (
                 ValueType::Public(PlaintextType::Literal(LiteralType::Boolean)),
                 UserInputValueType::Boolean(b),
@@ -405,6 +652,14 @@ pub(crate) fn process_inputs(
                 Namespace::new(cs.clone(), None),
                 || Ok(b),
             )?),
+            // Public String
+            (
+                ValueType::Public(PlaintextType::Literal(LiteralType::String)),
+                UserInputValueType::String(string),
+            ) => SimpleString(StringGadget::new_input(
+                Namespace::new(cs.clone(), None),
+                || Ok(string),
+            )?),
             // Private UInt
             (
                 ValueType::Private(PlaintextType::Literal(LiteralType::U8)),
@@ -466,6 +721,14 @@ pub(crate) fn process_inputs(
                 Namespace::new(cs.clone(), None),
                 || Ok(b),
             )?),
+            // Private String
+            (
+                ValueType::Private(PlaintextType::Literal(LiteralType::String)),
+                UserInputValueType::String(string),
+            ) => SimpleString(StringGadget::new_witness(
+                Namespace::new(cs.clone(), None),
+                || Ok(string),
+            )?),
             // Literal Type Error Cases
             (
                 ValueType::Private(PlaintextType::Literal(
@@ -474,7 +737,8 @@ pub(crate) fn process_inputs(
                     | LiteralType::U32
                     | LiteralType::U16
                     | LiteralType::U8
-                    | LiteralType::I8,
+                    | LiteralType::I8
+                    | LiteralType::String,
                 ))
                 | ValueType::Public(PlaintextType::Literal(
                     LiteralType::Address
@@ -482,84 +746,69 @@ pub(crate) fn process_inputs(
                     | LiteralType::U32
                     | LiteralType::U16
                     | LiteralType::U8
-                    | LiteralType::I8,
+                    | LiteralType::I8
+                    | LiteralType::String,
                 )),
                 _,
             ) => {
                 bail!("Mismatched function input type with user input type")
             }
+            // Public Struct
+            (
+                ValueType::Public(PlaintextType::Interface(interface_identifier)),
+                UserInputValueType::Struct(members),
+            ) => {
+                check_struct_members(program, interface_identifier, members)?;
+                allocate_user_input(cs, user_input, AllocationMode::Input)?
+            }
+            // Private Struct
+            (
+                ValueType::Private(PlaintextType::Interface(interface_identifier)),
+                UserInputValueType::Struct(members),
+            ) => {
+                check_struct_members(program, interface_identifier, members)?;
+                allocate_user_input(cs, user_input, AllocationMode::Witness)?
+            }
+            // Struct Type Error Cases
+            (
+                ValueType::Public(PlaintextType::Interface(_))
+                | ValueType::Private(PlaintextType::Interface(_)),
+                _,
+            ) => {
+                bail!("Mismatched function input type with user input type")
+            }
             // Unsupported Cases
             (ValueType::Public(v) | ValueType::Private(v), t) => {
                 println!("UNSUPPORTED TYPE: {v:?} {t:?}");
                 bail!("Unsupported type")
             }
             // Records
-            (
-                ValueType::Record(_),
-                UserInputValueType::Record(JAleoRecord {
-                    owner: address,
-                    gates,
-                    data,
-                    nonce,
-                }),
-            ) => {
-                let mut entries_gadgets: IndexMap<String, CircuitIOType> = IndexMap::new();
-                for (k, v) in data {
-                    let entry = match v {
-                        UserInputValueType::U8(v) => SimpleUInt8(UInt8Gadget::new_witness(
-                            Namespace::new(cs.clone(), None),
-                            || Ok(v),
-                        )?),
-                        UserInputValueType::I8(v) => SimpleInt8(Int8Gadget::new_witness(
-                            Namespace::new(cs.clone(), None),
-                            || Ok(v),
-                        )?),
-                        UserInputValueType::U16(v) => SimpleUInt16(UInt16Gadget::new_witness(
-                            Namespace::new(cs.clone(), None),
-                            || Ok(v),
-                        )?),
-                        UserInputValueType::U32(v) => SimpleUInt32(UInt32Gadget::new_witness(
-                            Namespace::new(cs.clone(), None),
-                            || Ok(v),
-                        )?),
-                        UserInputValueType::U64(v) => SimpleUInt64(UInt64Gadget::new_witness(
-                            Namespace::new(cs.clone(), None),
-                            || Ok(v),
-                        )?),
-                        UserInputValueType::U128(_) => bail!("U128 is not supported"),
-                        UserInputValueType::Address(a) => SimpleAddress(
-                            AddressGadget::new_witness(Namespace::new(cs.clone(), None), || Ok(a))?,
-                        ),
-                        UserInputValueType::Record(_) => bail!("Nested records are not supported"),
-                        UserInputValueType::Boolean(v) => SimpleBoolean(Boolean::new_witness(
-                            Namespace::new(cs.clone(), None),
-                            || Ok(v),
-                        )?),
-                        UserInputValueType::Field(f) => SimpleField(FieldGadget::new_witness(
-                            Namespace::new(cs.clone(), None),
-                            || Ok(f),
-                        )?),
-                    };
-                    entries_gadgets.insert(k.to_owned(), entry);
-                }
-                SimpleRecord(VMRecord {
-                    owner: AddressGadget::new_witness(Namespace::new(cs.clone(), None), || {
-                        Ok(address)
-                    })?,
-                    gates: UInt64Gadget::new_witness(Namespace::new(cs.clone(), None), || {
-                        Ok(gates)
-                    })?,
-                    entries: entries_gadgets,
-                    nonce: *nonce,
-                })
+            (ValueType::Record(record_identifier), UserInputValueType::Record(record)) => {
+                let aleo_record = program.get_record(record_identifier)?;
+                check_record_entries(program, aleo_record.entries(), &record.data)?;
+                allocate_record(cs, record)?
             }
             (ValueType::Record(_), _) => {
                 bail!("Mismatched function input type with user input type")
             }
             // Constant Types
-            (ValueType::Constant(_), _) => bail!("Constant types are not supported"),
+            (ValueType::Constant(plaintext_type), _) => {
+                ensure!(
+                    plaintext_type_matches(program, plaintext_type, user_input)?,
+                    "Mismatched function input type with user input type"
+                );
+                allocate_user_input(cs, user_input, AllocationMode::Constant)?
+            }
             // External Records
-            (ValueType::ExternalRecord(_), _) => bail!("ExternalRecord types are not supported"),
+            (ValueType::ExternalRecord(locator), UserInputValueType::Record(record)) => {
+                let external_program = external_program(program, imports, locator)?;
+                let aleo_record = external_program.get_record(locator.resource())?;
+                check_record_entries(external_program, aleo_record.entries(), &record.data)?;
+                allocate_record(cs, record)?
+            }
+            (ValueType::ExternalRecord(_), _) => {
+                bail!("Mismatched function input type with user input type")
+            }
         };
         program_variables.insert(register.to_string(), Some(circuit_input));
     }
@@ -567,6 +816,99 @@ pub(crate) fn process_inputs(
     Ok(())
 }
 
+/// Allocates the given record in the given constraint system. The owner, the
+/// gates and the private entries are witnesses, the public entries are public
+/// inputs and the constant ones constants.
+fn allocate_record(cs: &ConstraintSystemRef, record: &JAleoRecord) -> Result<CircuitIOType> {
+    let mut entries_gadgets: IndexMap<String, CircuitIOType> = IndexMap::new();
+    for (k, v) in &record.data {
+        let mode = match v.visibility {
+            Visibility::Constant => AllocationMode::Constant,
+            Visibility::Public => AllocationMode::Input,
+            Visibility::Private => AllocationMode::Witness,
+        };
+        let entry = allocate_user_input(cs, &v.value, mode)?;
+        entries_gadgets.insert(k.to_owned(), entry);
+    }
+    Ok(SimpleRecord(VMRecord {
+        owner: AddressGadget::new_witness(Namespace::new(cs.clone(), None), || Ok(record.owner))?,
+        gates: UInt64Gadget::new_witness(Namespace::new(cs.clone(), None), || Ok(record.gates))?,
+        entries: entries_gadgets,
+        nonce: record.nonce,
+    }))
+}
+
+/// Allocates a record entry or a struct member in the given constraint system
+/// with the given allocation mode. Structs are allocated member by member.
+fn allocate_user_input(
+    cs: &ConstraintSystemRef,
+    user_input: &UserInputValueType,
+    mode: AllocationMode,
+) -> Result<CircuitIOType> {
+    let circuit_input = match user_input {
+        UserInputValueType::U8(v) => SimpleUInt8(UInt8Gadget::new_variable(
+            Namespace::new(cs.clone(), None),
+            || Ok(v),
+            mode,
+        )?),
+        UserInputValueType::I8(v) => SimpleInt8(Int8Gadget::new_variable(
+            Namespace::new(cs.clone(), None),
+            || Ok(v),
+            mode,
+        )?),
+        UserInputValueType::U16(v) => SimpleUInt16(UInt16Gadget::new_variable(
+            Namespace::new(cs.clone(), None),
+            || Ok(v),
+            mode,
+        )?),
+        UserInputValueType::U32(v) => SimpleUInt32(UInt32Gadget::new_variable(
+            Namespace::new(cs.clone(), None),
+            || Ok(v),
+            mode,
+        )?),
+        UserInputValueType::U64(v) => SimpleUInt64(UInt64Gadget::new_variable(
+            Namespace::new(cs.clone(), None),
+            || Ok(v),
+            mode,
+        )?),
+        UserInputValueType::U128(_) => bail!("U128 is not supported"),
+        UserInputValueType::Address(a) => SimpleAddress(AddressGadget::new_variable(
+            Namespace::new(cs.clone(), None),
+            || Ok(a),
+            mode,
+        )?),
+        UserInputValueType::Record(_) => bail!("Nested records are not supported"),
+        UserInputValueType::Boolean(v) => SimpleBoolean(Boolean::new_variable(
+            Namespace::new(cs.clone(), None),
+            || Ok(v),
+            mode,
+        )?),
+        UserInputValueType::Field(f) => SimpleField(FieldGadget::new_variable(
+            Namespace::new(cs.clone(), None),
+            || Ok(f),
+            mode,
+        )?),
+        UserInputValueType::Struct(members) => {
+            let mut members_gadgets = VMStructMembersMap::new();
+            for (member_name, member_value) in members {
+                members_gadgets.insert(
+                    member_name.to_owned(),
+                    allocate_user_input(cs, member_value, mode)?,
+                );
+            }
+            SimpleStruct(members_gadgets)
+        }
+        UserInputValueType::String(string) => SimpleString(StringGadget::new_variable(
+            Namespace::new(cs.clone(), None),
+            || Ok(string),
+            mode,
+        )?),
+        UserInputValueType::Group(_) => bail!("Group is not supported"),
+        UserInputValueType::Scalar(_) => bail!("Scalar is not supported"),
+    };
+    Ok(circuit_input)
+}
+
 /// Executes the given function's instructions, adding the necessary constraints for each one and filling in
 /// all the variables in the given `program_variables` index map. Instructions such as `asserts` do not have
 /// outputs and while they add constraints to ensure the values are as expected, they don't modify the program
@@ -591,85 +933,148 @@ pub(crate) fn process_inputs(
 ///
 pub(crate) fn process_outputs(
     program: &Program,
+    imports: &[Program],
     function: &Function<Testnet3>,
     program_variables: &mut SimpleFunctionVariables,
     constraint_system: ConstraintSystemRef,
 ) -> Result<()> {
     for instruction in function.instructions() {
-        let operands = process_operands(instruction.operands(), program_variables)?;
-        let circuit_output = match instruction {
-            Instruction::Add(_) => instructions::add(&operands)?,
-            // because asserts don't really have outputs, continue the loop on asserts
-            Instruction::AssertEq(_) => {
-                instructions::assert_eq(&operands)?;
-                continue;
-            }
-            Instruction::AssertNeq(_) => {
-                instructions::assert_neq(&operands)?;
-                continue;
-            }
-            Instruction::And(_) => instructions::and(&operands)?,
-            Instruction::Cast(cast) => match cast.register_type() {
-                snarkvm::prelude::RegisterType::Record(record_identifier) => {
-                    let aleo_record = program.get_record(record_identifier)?;
-                    let aleo_record_entries = aleo_record.entries();
-                    instructions::cast(
-                        instruction.operands(),
-                        program_variables,
-                        aleo_record_entries,
-                        constraint_system.clone(),
-                    )?
-                }
-                _ => bail!("Cast is not supported for non-record types"),
-            },
-            Instruction::Div(_) => instructions::div(&operands, constraint_system.clone())?,
-            Instruction::GreaterThan(_) => instructions::compare(
-                &operands,
-                constraint_system.clone(),
-                Comparison::GreaterThan,
-            )?,
-            Instruction::GreaterThanOrEqual(_) => instructions::compare(
-                &operands,
-                constraint_system.clone(),
-                Comparison::GreaterThanOrEqual,
-            )?,
-            Instruction::HashPSD2(_) => instructions::hash_psd2(&operands)?,
-            Instruction::IsEq(_) => instructions::is_eq(&operands)?,
-            Instruction::IsNeq(_) => instructions::is_neq(&operands)?,
-            Instruction::LessThan(_) => {
-                instructions::compare(&operands, constraint_system.clone(), Comparison::LessThan)?
-            }
-            Instruction::LessThanOrEqual(_) => instructions::compare(
-                &operands,
-                constraint_system.clone(),
-                Comparison::LessThanOrEqual,
-            )?,
-            Instruction::Nand(_) => instructions::nand(&operands)?,
-            Instruction::Or(_) => instructions::or(&operands)?,
-            Instruction::Xor(_) => instructions::xor(&operands)?,
-            Instruction::Nor(_) => instructions::nor(&operands)?,
-            Instruction::Mul(_) => instructions::mul(&operands, constraint_system.clone())?,
-            Instruction::Shl(_) => instructions::shl(&operands, constraint_system.clone())?,
-            Instruction::Shr(_) => instructions::shr(&operands, constraint_system.clone())?,
-            Instruction::Sub(_) => instructions::sub(&operands)?,
-            Instruction::Ternary(_) => instructions::ternary(&operands)?,
-            _ => bail!(
-                "{} instruction is not supported currently",
-                instruction.opcode()
-            ),
-        };
+        process_instruction(
+            program,
+            instruction,
+            program_variables,
+            constraint_system.clone(),
+        )?;
+    }
 
-        let destination = instruction
-            .destinations()
-            .get(0)
-            .ok_or_else(|| anyhow!("Error getting the destination register"))?
-            .to_string();
+    // Constant outputs are part of the circuit, so they cannot depend on any
+    // input that is not a constant.
+    for function_output in function.outputs() {
+        if let ValueType::Constant(_) = function_output.value_type() {
+            let output = register_value(program_variables, function_output.register())?;
+            ensure!(
+                output.is_constant(),
+                "Output {} is declared as constant but its value is not a constant",
+                function_output.register()
+            );
+        }
+    }
 
-        program_variables.insert(destination, Some(circuit_output));
+    // The public entries of output records are public inputs in the order of
+    // the outputs, which is the order the verifier takes them from the
+    // transition in, whatever the order the records were cast in.
+    for function_output in function.outputs() {
+        if let SimpleRecord(record) = register_value(program_variables, function_output.register())?
+        {
+            let entry_types = record_entry_types(program, imports, function_output.value_type())?;
+            for (entry_name, entry_type) in &entry_types {
+                if let EntryType::Public(_) = entry_type {
+                    let entry = record.entries.get(&entry_name.to_string()).ok_or_else(|| {
+                        anyhow!(
+                            "Entry {entry_name} of output {} not found",
+                            function_output.register()
+                        )
+                    })?;
+                    instructions::allocate_entry(entry, AllocationMode::Input, &constraint_system)?;
+                }
+            }
+        }
     }
     Ok(())
 }
 
+/// Adds the constraints of a single instruction to the constraint system and
+/// stores its output in its destination register, which is returned. Asserts
+/// have no destination.
+pub(crate) fn process_instruction(
+    program: &Program,
+    instruction: &Instruction<Testnet3>,
+    program_variables: &mut SimpleFunctionVariables,
+    constraint_system: ConstraintSystemRef,
+) -> Result<Option<String>> {
+    let operands = process_operands(instruction.operands(), program_variables)?;
+    let circuit_output = match instruction {
+        Instruction::Add(_) => instructions::add(&operands)?,
+        // because asserts don't really have outputs, there is no destination to return
+        Instruction::AssertEq(_) => {
+            instructions::assert_eq(&operands)?;
+            return Ok(None);
+        }
+        Instruction::AssertNeq(_) => {
+            instructions::assert_neq(&operands)?;
+            return Ok(None);
+        }
+        Instruction::And(_) => instructions::and(&operands)?,
+        Instruction::Cast(cast) => match cast.register_type() {
+            snarkvm::prelude::RegisterType::Record(record_identifier) => {
+                let aleo_record = program.get_record(record_identifier)?;
+                let aleo_record_entries = aleo_record.entries();
+                instructions::cast(
+                    program,
+                    instruction.operands(),
+                    program_variables,
+                    aleo_record_entries,
+                    constraint_system.clone(),
+                )?
+            }
+            snarkvm::prelude::RegisterType::Plaintext(PlaintextType::Literal(literal_type)) => {
+                instructions::cast_into_literal(&operands, literal_type)?
+            }
+            snarkvm::prelude::RegisterType::Plaintext(PlaintextType::Interface(
+                interface_identifier,
+            )) => {
+                let interface = program.get_interface(interface_identifier)?;
+                instructions::cast_into_struct(program, &operands, interface.members())?
+            }
+            _ => bail!("Cast is not supported for the given register type"),
+        },
+        Instruction::Div(_) => instructions::div(&operands, constraint_system.clone())?,
+        Instruction::GreaterThan(_) => instructions::compare(
+            &operands,
+            constraint_system.clone(),
+            Comparison::GreaterThan,
+        )?,
+        Instruction::GreaterThanOrEqual(_) => instructions::compare(
+            &operands,
+            constraint_system.clone(),
+            Comparison::GreaterThanOrEqual,
+        )?,
+        Instruction::HashPSD2(_) => instructions::hash_psd2(&operands)?,
+        Instruction::IsEq(_) => instructions::is_eq(&operands)?,
+        Instruction::IsNeq(_) => instructions::is_neq(&operands)?,
+        Instruction::LessThan(_) => {
+            instructions::compare(&operands, constraint_system.clone(), Comparison::LessThan)?
+        }
+        Instruction::LessThanOrEqual(_) => instructions::compare(
+            &operands,
+            constraint_system.clone(),
+            Comparison::LessThanOrEqual,
+        )?,
+        Instruction::Nand(_) => instructions::nand(&operands)?,
+        Instruction::Or(_) => instructions::or(&operands)?,
+        Instruction::Xor(_) => instructions::xor(&operands)?,
+        Instruction::Nor(_) => instructions::nor(&operands)?,
+        Instruction::Mul(_) => instructions::mul(&operands, constraint_system.clone())?,
+        Instruction::Shl(_) => instructions::shl(&operands, constraint_system.clone())?,
+        Instruction::Shr(_) => instructions::shr(&operands, constraint_system.clone())?,
+        Instruction::Sub(_) => instructions::sub(&operands)?,
+        Instruction::Ternary(_) => instructions::ternary(&operands)?,
+        _ => bail!(
+            "{} instruction is not supported currently",
+            instruction.opcode()
+        ),
+    };
+
+    let destination = instruction
+        .destinations()
+        .get(0)
+        .ok_or_else(|| anyhow!("Error getting the destination register"))?
+        .to_string();
+
+    program_variables.insert(destination.clone(), Some(circuit_output));
+    Ok(Some(destination))
+}
+
 pub fn process_operands(
     operands: &[Operand<Testnet3>],
     program_variables: &mut IndexMap<String, Option<CircuitIOType>>,
@@ -682,41 +1087,14 @@ pub fn process_operands(
         let variable_name = &operand.to_string();
         match (operand, program_variables.get(variable_name)) {
             (Operand::Register(Register::Member(locator, members)), Some(None)) => {
-                if let Some(Some(SimpleRecord(record))) =
-                    program_variables.get(&format!("r{locator}"))
-                {
-                    match members
-                        .get(0)
-                        .ok_or("Error getting the first member of a register member")
-                        .map_err(|e| anyhow!("{}", e))?
-                        .to_string()
-                        .as_str()
-                    {
-                        "owner" => {
-                            let owner_operand = SimpleAddress(record.owner.clone());
-                            program_variables
-                                .insert(variable_name.to_string(), Some(owner_operand.clone()));
-                            instruction_operands.insert(variable_name.to_owned(), owner_operand);
-                        }
-                        "gates" => {
-                            let gates_operand = SimpleUInt64(record.gates.clone());
-                            program_variables
-                                .insert(variable_name.to_string(), Some(gates_operand.clone()));
-                            instruction_operands.insert(variable_name.to_owned(), gates_operand);
-                        }
-                        entry => {
-                            let entry_operand = record
-                                .entries
-                                .get(entry)
-                                .ok_or(format!("Could not find entry `{entry}` in record entries map. Record entries are {entries:?}", entries = record.entries.keys()))
-                                .map_err(|e| anyhow!("{e}"))?
-                                .clone();
-                            program_variables
-                                .insert(variable_name.to_string(), Some(entry_operand.clone()));
-                            instruction_operands.insert(entry.to_owned(), entry_operand);
-                        }
-                    };
-                }
+                let register = program_variables
+                    .get(&format!("r{locator}"))
+                    .cloned()
+                    .flatten()
+                    .ok_or_else(|| anyhow!("Register \"r{locator}\" not assigned in registers"))?;
+                let member_operand = member_value(&register, members)?;
+                program_variables.insert(variable_name.to_string(), Some(member_operand.clone()));
+                instruction_operands.insert(variable_name.to_owned(), member_operand);
             }
             (Operand::Register(_), Some(Some(operand))) => {
                 instruction_operands.insert(variable_name.to_owned(), operand.clone());
@@ -783,6 +1161,13 @@ pub fn process_operands(
                 "Literal \"{}\"i8 not assigned in registers",
                 Operand::Literal(Literal::I8(*v))
             ),
+            (Operand::Literal(Literal::String(literal_value)), Some(Some(v))) => {
+                instruction_operands.insert(format!("\"{}\"", **literal_value), v.clone());
+            }
+            (Operand::Literal(Literal::String(v)), Some(None)) => bail!(
+                "Literal \"{}\" not assigned in registers",
+                Operand::Literal(Literal::String(v.clone()))
+            ),
             (Operand::Literal(_), _) => bail!("Literal operand not supported"),
             (Operand::ProgramID(_), _) => bail!("ProgramID operands are not supported"),
             (Operand::Caller, _) => bail!("Caller operands are not supported"),
@@ -791,6 +1176,64 @@ pub fn process_operands(
     Ok(instruction_operands)
 }
 
+/// Returns the value of the given register, which may be a member of a record
+/// or struct register like `r7.owner` or `r7.point.x`.
+pub fn register_value(
+    program_variables: &SimpleFunctionVariables,
+    register: &Register<Testnet3>,
+) -> Result<CircuitIOType> {
+    let register_variable = format!("r{}", register.locator());
+    let value = program_variables
+        .get(&register_variable)
+        .ok_or_else(|| anyhow!("Register \"{register_variable}\" not found"))?
+        .clone()
+        .ok_or_else(|| anyhow!("Register \"{register}\" not assigned"))?;
+    match register {
+        Register::Member(_, members) => member_value(&value, members),
+        Register::Locator(_) => Ok(value),
+    }
+}
+
+/// Returns the value at the given member path of a record or struct register,
+/// e.g. `[gates]` for `r0.gates` or `[b, c]` for `r0.b.c`.
+pub fn member_value(register: &CircuitIOType, members: &[Identifier]) -> Result<CircuitIOType> {
+    let (member, rest) = members
+        .split_first()
+        .ok_or_else(|| anyhow!("Error getting the first member of a register member"))?;
+    let member_name = member.to_string();
+    let value = match register {
+        SimpleRecord(record) => match member_name.as_str() {
+            "owner" => SimpleAddress(record.owner.clone()),
+            "gates" => SimpleUInt64(record.gates.clone()),
+            entry => record
+                .entries
+                .get(entry)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Could not find entry `{entry}` in record entries map. Record entries are {entries:?}",
+                        entries = record.entries.keys()
+                    )
+                })?
+                .clone(),
+        },
+        SimpleStruct(struct_members) => struct_members
+            .get(&member_name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Could not find member `{member_name}` in struct members. Struct members are {members:?}",
+                    members = struct_members.keys()
+                )
+            })?
+            .clone(),
+        _ => bail!("Cannot access member `{member_name}` of a register that is neither a record nor a struct"),
+    };
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        member_value(&value, rest)
+    }
+}
+
 pub fn random_nonce() -> Group<Testnet3> {
     let rng = &mut rand::thread_rng();
     let randomizer = Scalar::rand(rng);
@@ -6,7 +6,7 @@ use ark_r1cs_std::{prelude::EqGadget, R1CSVar};
 use indexmap::IndexMap;
 use simpleworks::gadgets::ConstraintF;
 pub use CircuitIOType::{
-    SimpleBoolean, SimpleInt8, SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8,
+    SimpleBoolean, SimpleInt8, SimpleString, SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8,
 };
 pub fn assert_eq(operands: &IndexMap<String, CircuitIOType>) -> Result<()> {
     match operands
@@ -29,6 +29,9 @@ pub fn assert_eq(operands: &IndexMap<String, CircuitIOType>) -> Result<()> {
         [SimpleInt8(left_operand), SimpleInt8(right_operand)] => {
             assert_and_enforce_equal(left_operand, right_operand)
         }
+        [SimpleString(left_operand), SimpleString(right_operand)] => {
+            assert_and_enforce_equal(left_operand, right_operand)
+        }
         [_, _] => bail!("assert.eq is not supported for the given types"),
         [..] => bail!("assert.eq requires two operands"),
     }
@@ -55,6 +58,9 @@ pub fn assert_neq(operands: &IndexMap<String, CircuitIOType>) -> Result<()> {
         [SimpleInt8(left_operand), SimpleInt8(right_operand)] => {
             assert_and_enforce_not_equal(left_operand, right_operand)
         }
+        [SimpleString(left_operand), SimpleString(right_operand)] => {
+            assert_and_enforce_not_equal(left_operand, right_operand)
+        }
         [_, _] => bail!("assert.neq is not supported for the given types"),
         [..] => bail!("assert.neq requires two operands"),
     }
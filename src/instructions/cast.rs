@@ -1,25 +1,33 @@
-use std::str::FromStr;
-
 use crate::{
-    circuit_io_type::CircuitIOType, helpers, record::Record, Int8Gadget, UInt16Gadget,
-    UInt32Gadget, UInt64Gadget, UInt8Gadget, VMRecordEntriesMap,
+    circuit_io_type::CircuitIOType,
+    helpers,
+    jaleo::{primitive_struct_members, Program},
+    record::Record,
+    Int8Gadget, UInt16Gadget, UInt32Gadget, UInt64Gadget, UInt8Gadget, VMRecordEntriesMap,
+    VMStructMembersMap,
 };
 use anyhow::{anyhow, bail, Result};
 use ark_r1cs_std::{
-    prelude::{AllocVar, Boolean},
+    fields::fp::FpVar,
+    prelude::{AllocVar, AllocationMode, Boolean, EqGadget, ToBitsGadget, ToBytesGadget},
     R1CSVar,
 };
+use ark_relations::r1cs::SynthesisError;
 use indexmap::IndexMap;
 use simpleworks::{
-    gadgets::{traits::IsWitness, AddressGadget, ConstraintF, FieldGadget},
+    gadgets::{traits::IsWitness, ConstraintF},
     marlin::ConstraintSystemRef,
 };
 use snarkvm::prelude::{
     EntryType, Identifier, Literal, LiteralType, Operand, PlaintextType, Register, Testnet3,
 };
-pub use CircuitIOType::{SimpleAddress, SimpleRecord, SimpleUInt64};
+pub use CircuitIOType::{
+    SimpleAddress, SimpleBoolean, SimpleField, SimpleInt8, SimpleRecord, SimpleString,
+    SimpleStruct, SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8,
+};
 
 pub fn cast(
+    program: &Program,
     operands: &[Operand<Testnet3>],
     program_variables: &mut IndexMap<String, Option<CircuitIOType>>,
     aleo_record_entries: &IndexMap<Identifier<Testnet3>, EntryType<Testnet3>>,
@@ -34,200 +42,34 @@ pub fn cast(
         match (operand, program_variables.get(variable_name)) {
             // Handle register members
             (Operand::Register(Register::Member(locator, members)), Some(None)) => {
-                if let Some(Some(SimpleRecord(record))) =
-                    program_variables.get(&format!("r{locator}"))
-                {
-                    match members
-                        .get(0)
-                        .ok_or("Error getting the first member of a register member")
-                        .map_err(|e| anyhow!("{}", e))?
-                        .to_string()
-                        .as_str()
-                    {
-                        "owner" => {
-                            let owner_operand = SimpleAddress(record.owner.clone());
-                            program_variables
-                                .insert(variable_name.to_string(), Some(owner_operand.clone()));
-                            instruction_operands.insert(variable_name.to_owned(), owner_operand);
-                        }
-                        "gates" => {
-                            let gates_operand = SimpleUInt64(record.gates.clone());
-                            program_variables
-                                .insert(variable_name.to_string(), Some(gates_operand.clone()));
-                            instruction_operands.insert(variable_name.to_owned(), gates_operand);
-                        }
-                        entry => {
-                            let entry_operand = record
-                                .entries
-                                .get(entry)
-                                .ok_or(format!("Could not find entry `{entry}` in record entries map. Record entries are {entries:?}", entries = record.entries.keys()))
-                                .map_err(|e| anyhow!("{e}"))?
-                                .clone();
-                            program_variables
-                                .insert(variable_name.to_string(), Some(entry_operand.clone()));
-                            instruction_operands.insert(entry.to_owned(), entry_operand);
-                        }
-                    };
-                }
+                let register = program_variables
+                    .get(&format!("r{locator}"))
+                    .cloned()
+                    .flatten()
+                    .ok_or_else(|| anyhow!("Register \"r{locator}\" not assigned in registers"))?;
+                let member_operand = helpers::member_value(&register, members)?;
+                program_variables.insert(variable_name.to_string(), Some(member_operand.clone()));
+                let (operand_name, operand) = cast_operand(
+                    program,
+                    variable_name,
+                    operand_index,
+                    &member_operand,
+                    aleo_record_entries,
+                    &constraint_system,
+                )?;
+                instruction_operands.insert(operand_name, operand);
             }
             // Handle registers
             (Operand::Register(_), Some(Some(operand))) => {
-                // An operand is an entry of the record if the index is greater
-                // than 1 because the index 0 is always for the record owner and
-                // the index 1 is always for the record gates, the rest are entries.
-                let operand_is_entry = operand_index > 1;
-                if operand_is_entry {
-                    let entry_name = aleo_record_entries
-                        .keys()
-                        .map(|identifier| identifier.to_string())
-                        .collect::<Vec<String>>()
-                        .get(operand_index - 2)
-                        .ok_or_else(|| anyhow!("Error getting entry name from aleo entries"))?
-                        .clone();
-                    let entry = aleo_record_entries
-                        .get(&Identifier::from_str(&entry_name)?)
-                        .ok_or_else(|| anyhow!("Error getting entry name from aleo entries"))?;
-                    let entry_operand: Result<CircuitIOType> = match (entry, operand) {
-                        (
-                            EntryType::Constant(PlaintextType::Literal(LiteralType::Address)),
-                            CircuitIOType::SimpleAddress(operand_value),
-                        ) => Ok(CircuitIOType::SimpleAddress(AddressGadget::new_constant(
-                            constraint_system.clone(),
-                            helpers::to_address(operand_value.value()?),
-                        )?)),
-                        (
-                            EntryType::Constant(PlaintextType::Literal(LiteralType::Boolean)),
-                            CircuitIOType::SimpleBoolean(operand_value),
-                        ) => Ok(CircuitIOType::SimpleBoolean(
-                            Boolean::<ConstraintF>::new_constant(
-                                constraint_system.clone(),
-                                operand_value.value()?,
-                            )?,
-                        )),
-                        (
-                            EntryType::Constant(PlaintextType::Literal(LiteralType::Field)),
-                            CircuitIOType::SimpleField(operand_value),
-                        ) => Ok(CircuitIOType::SimpleField(FieldGadget::new_constant(
-                            constraint_system.clone(),
-                            operand_value.value()?,
-                        )?)),
-                        (
-                            EntryType::Constant(PlaintextType::Literal(LiteralType::U8)),
-                            CircuitIOType::SimpleUInt8(operand_value),
-                        ) => Ok(CircuitIOType::SimpleUInt8(UInt8Gadget::new_constant(
-                            constraint_system.clone(),
-                            operand_value.value()?,
-                        )?)),
-                        (
-                            EntryType::Constant(PlaintextType::Literal(LiteralType::I8)),
-                            CircuitIOType::SimpleInt8(operand_value),
-                        ) => Ok(CircuitIOType::SimpleInt8(Int8Gadget::new_constant(
-                            constraint_system.clone(),
-                            operand_value.value()?,
-                        )?)),
-                        (
-                            EntryType::Constant(PlaintextType::Literal(LiteralType::U16)),
-                            CircuitIOType::SimpleUInt16(operand_value),
-                        ) => Ok(CircuitIOType::SimpleUInt16(UInt16Gadget::new_constant(
-                            constraint_system.clone(),
-                            operand_value.value()?,
-                        )?)),
-                        (
-                            EntryType::Constant(PlaintextType::Literal(LiteralType::U32)),
-                            CircuitIOType::SimpleUInt32(operand_value),
-                        ) => Ok(CircuitIOType::SimpleUInt32(UInt32Gadget::new_constant(
-                            constraint_system.clone(),
-                            operand_value.value()?,
-                        )?)),
-                        (
-                            EntryType::Constant(PlaintextType::Literal(LiteralType::U64)),
-                            CircuitIOType::SimpleUInt64(operand_value),
-                        ) => Ok(CircuitIOType::SimpleUInt64(UInt64Gadget::new_constant(
-                            constraint_system.clone(),
-                            operand_value.value()?,
-                        )?)),
-                        (
-                            EntryType::Public(PlaintextType::Literal(LiteralType::Address))
-                            | EntryType::Private(PlaintextType::Literal(LiteralType::Address)),
-                            CircuitIOType::SimpleAddress(operand_value),
-                        ) => Ok(CircuitIOType::SimpleAddress(AddressGadget::new_witness(
-                            constraint_system.clone(),
-                            || Ok(helpers::to_address(operand_value.value()?)),
-                        )?)),
-                        (
-                            EntryType::Public(PlaintextType::Literal(LiteralType::Boolean))
-                            | EntryType::Private(PlaintextType::Literal(LiteralType::Boolean)),
-                            CircuitIOType::SimpleBoolean(operand_value),
-                        ) => Ok(CircuitIOType::SimpleBoolean(
-                            Boolean::<ConstraintF>::new_witness(constraint_system.clone(), || {
-                                operand_value.value()
-                            })?,
-                        )),
-                        (
-                            EntryType::Public(PlaintextType::Literal(LiteralType::Field))
-                            | EntryType::Private(PlaintextType::Literal(LiteralType::Field)),
-                            CircuitIOType::SimpleField(operand_value),
-                        ) => Ok(CircuitIOType::SimpleField(FieldGadget::new_witness(
-                            constraint_system.clone(),
-                            || operand_value.value(),
-                        )?)),
-                        (
-                            EntryType::Public(PlaintextType::Literal(LiteralType::U8))
-                            | EntryType::Private(PlaintextType::Literal(LiteralType::U8)),
-                            CircuitIOType::SimpleUInt8(operand_value),
-                        ) => Ok(CircuitIOType::SimpleUInt8(UInt8Gadget::new_witness(
-                            constraint_system.clone(),
-                            || operand_value.value(),
-                        )?)),
-                        (
-                            EntryType::Public(PlaintextType::Literal(LiteralType::I8))
-                            | EntryType::Private(PlaintextType::Literal(LiteralType::I8)),
-                            CircuitIOType::SimpleInt8(operand_value),
-                        ) => Ok(CircuitIOType::SimpleInt8(Int8Gadget::new_witness(
-                            constraint_system.clone(),
-                            || operand_value.value(),
-                        )?)),
-                        (
-                            EntryType::Public(PlaintextType::Literal(LiteralType::U16))
-                            | EntryType::Private(PlaintextType::Literal(LiteralType::U16)),
-                            CircuitIOType::SimpleUInt16(operand_value),
-                        ) => Ok(CircuitIOType::SimpleUInt16(UInt16Gadget::new_witness(
-                            constraint_system.clone(),
-                            || operand_value.value(),
-                        )?)),
-                        (
-                            EntryType::Public(PlaintextType::Literal(LiteralType::U32))
-                            | EntryType::Private(PlaintextType::Literal(LiteralType::U32)),
-                            CircuitIOType::SimpleUInt32(operand_value),
-                        ) => Ok(CircuitIOType::SimpleUInt32(UInt32Gadget::new_witness(
-                            constraint_system.clone(),
-                            || operand_value.value(),
-                        )?)),
-                        (
-                            EntryType::Public(PlaintextType::Literal(LiteralType::U64))
-                            | EntryType::Private(PlaintextType::Literal(LiteralType::U64)),
-                            CircuitIOType::SimpleUInt64(operand_value),
-                        ) => Ok(CircuitIOType::SimpleUInt64(UInt64Gadget::new_witness(
-                            constraint_system.clone(),
-                            || operand_value.value(),
-                        )?)),
-                        (
-                            EntryType::Constant(PlaintextType::Literal(_))
-                            | EntryType::Public(PlaintextType::Literal(_))
-                            | EntryType::Private(PlaintextType::Literal(_)),
-                            _,
-                        ) => bail!("Unsupported literal type as entry"),
-                        (
-                            EntryType::Constant(PlaintextType::Interface(_))
-                            | EntryType::Public(PlaintextType::Interface(_))
-                            | EntryType::Private(PlaintextType::Interface(_)),
-                            _,
-                        ) => bail!("Interface types are not supported yet as entries"),
-                    };
-                    instruction_operands.insert(entry_name, entry_operand?);
-                } else {
-                    instruction_operands.insert(variable_name.to_owned(), operand.clone());
-                }
+                let (operand_name, operand) = cast_operand(
+                    program,
+                    variable_name,
+                    operand_index,
+                    operand,
+                    aleo_record_entries,
+                    &constraint_system,
+                )?;
+                instruction_operands.insert(operand_name, operand);
             }
             (Operand::Register(r), Some(None)) => {
                 bail!("Register \"{}\" not assigned in registers", r.to_string())
@@ -299,6 +141,117 @@ pub fn cast(
     _cast(instruction_operands, constraint_system)
 }
 
+// Entries are named after the record entry they are cast into and allocated
+// again with its visibility, the rest of the operands are left as they are.
+fn cast_operand(
+    program: &Program,
+    variable_name: &str,
+    operand_index: usize,
+    operand: &CircuitIOType,
+    aleo_record_entries: &IndexMap<Identifier<Testnet3>, EntryType<Testnet3>>,
+    constraint_system: &ConstraintSystemRef,
+) -> Result<(String, CircuitIOType)> {
+    // An operand is an entry of the record if the index is greater
+    // than 1 because the index 0 is always for the record owner and
+    // the index 1 is always for the record gates, the rest are entries.
+    let operand_is_entry = operand_index > 1;
+    if !operand_is_entry {
+        return Ok((variable_name.to_owned(), operand.clone()));
+    }
+    let (entry_name, entry) = aleo_record_entries
+        .get_index(operand_index - 2)
+        .ok_or_else(|| anyhow!("Error getting entry name from aleo entries"))?;
+    // Public entries only become public inputs if the record is an output,
+    // which is done when processing the outputs so they follow their order.
+    let (plaintext_type, mode) = match entry {
+        EntryType::Constant(plaintext_type) => (plaintext_type, AllocationMode::Constant),
+        EntryType::Public(plaintext_type) | EntryType::Private(plaintext_type) => {
+            (plaintext_type, AllocationMode::Witness)
+        }
+    };
+    match (plaintext_type, operand) {
+        (PlaintextType::Literal(LiteralType::Address), SimpleAddress(_))
+        | (PlaintextType::Literal(LiteralType::Boolean), SimpleBoolean(_))
+        | (PlaintextType::Literal(LiteralType::Field), SimpleField(_))
+        | (PlaintextType::Literal(LiteralType::U8), SimpleUInt8(_))
+        | (PlaintextType::Literal(LiteralType::I8), SimpleInt8(_))
+        | (PlaintextType::Literal(LiteralType::U16), SimpleUInt16(_))
+        | (PlaintextType::Literal(LiteralType::U32), SimpleUInt32(_))
+        | (PlaintextType::Literal(LiteralType::U64), SimpleUInt64(_))
+        | (PlaintextType::Literal(LiteralType::String), SimpleString(_)) => {}
+        (PlaintextType::Interface(interface_identifier), SimpleStruct(members)) => {
+            helpers::check_struct_members(
+                program,
+                interface_identifier,
+                &primitive_struct_members(members.clone())?,
+            )
+            .map_err(|e| anyhow!("Invalid entry `{entry_name}`: {e}"))?;
+        }
+        (PlaintextType::Literal(_), _) => bail!("Unsupported literal type as entry"),
+        (PlaintextType::Interface(interface), _) => {
+            bail!("Entry `{entry_name}` of type `{interface}` must be a struct")
+        }
+    };
+    Ok((
+        entry_name.to_string(),
+        allocate_entry(operand, mode, constraint_system)?,
+    ))
+}
+
+// Struct entries are allocated member by member so that every member of
+// the struct gets the visibility of the entry.
+pub(crate) fn allocate_entry(
+    operand: &CircuitIOType,
+    mode: AllocationMode,
+    constraint_system: &ConstraintSystemRef,
+) -> Result<CircuitIOType> {
+    let cs = constraint_system.clone();
+    let entry = match operand {
+        SimpleAddress(value) => SimpleAddress(allocate_equal(
+            value,
+            || Ok(helpers::to_address(value.value()?)),
+            mode,
+            cs,
+        )?),
+        SimpleBoolean(value) => SimpleBoolean(allocate_equal(value, || value.value(), mode, cs)?),
+        SimpleField(value) => SimpleField(allocate_equal(value, || value.value(), mode, cs)?),
+        SimpleUInt8(value) => SimpleUInt8(allocate_equal(value, || value.value(), mode, cs)?),
+        SimpleInt8(value) => SimpleInt8(allocate_equal(value, || value.value(), mode, cs)?),
+        SimpleUInt16(value) => SimpleUInt16(allocate_equal(value, || value.value(), mode, cs)?),
+        SimpleUInt32(value) => SimpleUInt32(allocate_equal(value, || value.value(), mode, cs)?),
+        SimpleUInt64(value) => SimpleUInt64(allocate_equal(value, || value.value(), mode, cs)?),
+        SimpleString(value) => SimpleString(allocate_equal(value, || value.value(), mode, cs)?),
+        SimpleStruct(members) => {
+            let mut entry_members = VMStructMembersMap::new();
+            for (member_name, member) in members {
+                entry_members.insert(
+                    member_name.clone(),
+                    allocate_entry(member, mode, constraint_system)?,
+                );
+            }
+            SimpleStruct(entry_members)
+        }
+        SimpleRecord(_) => bail!("Records cannot be entries of other records"),
+    };
+    Ok(entry)
+}
+
+// The new variable is enforced to be equal to the operand, otherwise the
+// prover could put any value in the entry.
+fn allocate_equal<V, T>(
+    operand: &T,
+    value: impl FnOnce() -> Result<V, SynthesisError>,
+    mode: AllocationMode,
+    constraint_system: ConstraintSystemRef,
+) -> Result<T>
+where
+    T: AllocVar<V, ConstraintF> + EqGadget<ConstraintF>,
+{
+    let entry = T::new_variable(constraint_system, value, mode)?;
+    entry.enforce_equal(operand)?;
+    Ok(entry)
+}
+
 pub fn _cast(
     operands: IndexMap<String, CircuitIOType>,
     constraint_system: ConstraintSystemRef,
@@ -314,25 +267,27 @@ pub fn _cast(
                 entries.insert(key.to_owned(), value.clone());
             }
 
-            let (owner, gates) = match (owner.is_witness()?, gates.is_witness()?) {
-                (true, true) => (owner.clone(), gates.clone()),
-                (true, false) => {
-                    let gates = UInt64Gadget::new_witness(constraint_system, || gates.value())?;
-                    (owner.clone(), gates)
-                }
-                (false, true) => {
-                    let owner = AddressGadget::new_witness(constraint_system, || {
-                        Ok(helpers::to_address(owner.value()?))
-                    })?;
-                    (owner, gates.clone())
-                }
-                (false, false) => {
-                    let owner = AddressGadget::new_witness(constraint_system.clone(), || {
-                        Ok(helpers::to_address(owner.value()?))
-                    })?;
-                    let gates = UInt64Gadget::new_witness(constraint_system, || gates.value())?;
-                    (owner, gates)
-                }
+            // The owner and gates are private, so operands that are not
+            // witnesses are allocated again as witnesses equal to them.
+            let owner = if owner.is_witness()? {
+                owner.clone()
+            } else {
+                allocate_equal(
+                    owner,
+                    || Ok(helpers::to_address(owner.value()?)),
+                    AllocationMode::Witness,
+                    constraint_system.clone(),
+                )?
+            };
+            let gates = if gates.is_witness()? {
+                gates.clone()
+            } else {
+                allocate_equal(
+                    gates,
+                    || gates.value(),
+                    AllocationMode::Witness,
+                    constraint_system,
+                )?
             };
 
             Ok(SimpleRecord(Record {
@@ -347,17 +302,216 @@ pub fn _cast(
     }
 }
 
+/// Casts the operand into the given literal type following snarkVM's conversion
+/// rules. Conversions are done by value, so casting a negative integer into an
+/// unsigned type, a value that does not fit into a narrower type, or anything
+/// other than 0 or 1 into a boolean makes the circuit unsatisfiable. Negative
+/// integers cast into a field are `p - |x|`, as in snarkVM.
+pub fn cast_into_literal(
+    operands: &IndexMap<String, CircuitIOType>,
+    literal_type: &LiteralType,
+) -> Result<CircuitIOType> {
+    let operand = match operands
+        .values()
+        .collect::<Vec<&CircuitIOType>>()
+        .as_slice()
+    {
+        [operand] => (*operand).clone(),
+        [..] => bail!("Cast into {literal_type} requires one operand"),
+    };
+
+    let (bytes, sign) = match (operand, literal_type) {
+        // Casting into the same type is a no-op.
+        (SimpleUInt8(value), LiteralType::U8) => return Ok(SimpleUInt8(value)),
+        (SimpleUInt16(value), LiteralType::U16) => return Ok(SimpleUInt16(value)),
+        (SimpleUInt32(value), LiteralType::U32) => return Ok(SimpleUInt32(value)),
+        (SimpleUInt64(value), LiteralType::U64) => return Ok(SimpleUInt64(value)),
+        (SimpleInt8(value), LiteralType::I8) => return Ok(SimpleInt8(value)),
+        (SimpleBoolean(value), LiteralType::Boolean) => return Ok(SimpleBoolean(value)),
+        (SimpleField(value), LiteralType::Field) => return Ok(SimpleField(value)),
+        (SimpleAddress(value), LiteralType::Address) => return Ok(SimpleAddress(value)),
+        (SimpleString(value), LiteralType::String) => return Ok(SimpleString(value)),
+        (SimpleUInt8(value), _) => (vec![value], None),
+        (SimpleUInt16(value), _) => (value.to_bytes()?, None),
+        (SimpleUInt32(value), _) => (value.to_bytes()?, None),
+        (SimpleUInt64(value), _) => (value.to_bytes()?, None),
+        (SimpleInt8(value), _) => {
+            let bytes = value.to_bytes()?;
+            let sign = bytes
+                .to_bits_le()?
+                .last()
+                .cloned()
+                .ok_or_else(|| anyhow!("Error getting the sign bit of an i8"))?;
+            (bytes, Some(sign))
+        }
+        (SimpleBoolean(value), _) => (value.to_bytes()?, None),
+        (SimpleField(value), _) => (value.to_bytes()?, None),
+        (SimpleAddress(_) | SimpleRecord(_) | SimpleStruct(_) | SimpleString(_), _) => {
+            bail!("Cast into {literal_type} is not supported for the given type")
+        }
+    };
+
+    // Negative integers are only represented as field elements, otherwise
+    // they do not have a representation in any other type. The bytes of an
+    // i8 are its two's complement, which is 256 more than its value when it
+    // is negative.
+    if let Some(sign) = sign {
+        if matches!(literal_type, LiteralType::Field) {
+            let unsigned_value = Boolean::le_bits_to_fp_var(&bytes.to_bits_le()?)?;
+            return Ok(SimpleField(
+                unsigned_value - FpVar::from(sign) * ConstraintF::from(256_u64),
+            ));
+        }
+        enforce_lossless_cast(&sign.not())?;
+    }
+
+    // `value_bits` is the number of bits the value is allowed to use and
+    // `target_bits` is the size of the target type.
+    let mut bits = bytes.to_bits_le()?;
+    let (value_bits, target_bits) = match literal_type {
+        LiteralType::Boolean => (1, 1),
+        LiteralType::I8 => (7, 8),
+        LiteralType::U8 => (8, 8),
+        LiteralType::U16 => (16, 16),
+        LiteralType::U32 => (32, 32),
+        LiteralType::U64 => (64, 64),
+        LiteralType::Field => (bits.len(), bits.len()),
+        _ => bail!("Cast into {literal_type} is not supported"),
+    };
+
+    let overflow_bits = bits
+        .get(value_bits..)
+        .map(<[Boolean<ConstraintF>]>::to_vec)
+        .unwrap_or_default();
+    if !overflow_bits.is_empty() {
+        enforce_lossless_cast(&Boolean::kary_or(&overflow_bits)?.not())?;
+    }
+    bits.resize(target_bits, Boolean::FALSE);
+
+    match literal_type {
+        LiteralType::Boolean => Ok(SimpleBoolean(
+            bits.first()
+                .cloned()
+                .ok_or_else(|| anyhow!("Error getting the boolean bit"))?,
+        )),
+        LiteralType::I8 => Ok(SimpleInt8(int8_from_byte(&UInt8Gadget::from_bits_le(
+            &bits,
+        ))?)),
+        LiteralType::U8 => Ok(SimpleUInt8(UInt8Gadget::from_bits_le(&bits))),
+        LiteralType::U16 => Ok(SimpleUInt16(UInt16Gadget::from_bits_le(&bits))),
+        LiteralType::U32 => Ok(SimpleUInt32(UInt32Gadget::from_bits_le(&bits))),
+        LiteralType::U64 => Ok(SimpleUInt64(UInt64Gadget::from_bits_le(&bits))),
+        LiteralType::Field => Ok(SimpleField(Boolean::le_bits_to_fp_var(&bits)?)),
+        _ => bail!("Cast into {literal_type} is not supported"),
+    }
+}
+
+/// Casts the operands into a struct with the given interface members. Operands
+/// are assigned to the members in order and must match their types.
+pub fn cast_into_struct(
+    program: &Program,
+    operands: &IndexMap<String, CircuitIOType>,
+    interface_members: &IndexMap<Identifier<Testnet3>, PlaintextType<Testnet3>>,
+) -> Result<CircuitIOType> {
+    if operands.len() != interface_members.len() {
+        bail!(
+            "Cast into a struct requires {} operands",
+            interface_members.len()
+        );
+    }
+
+    let mut members = VMStructMembersMap::new();
+    for ((member_identifier, member_type), operand) in
+        interface_members.iter().zip(operands.values())
+    {
+        let member_matches = match (member_type, operand) {
+            // A nested struct has to match the definition of its interface.
+            (PlaintextType::Interface(interface_identifier), SimpleStruct(struct_members)) => {
+                helpers::check_struct_members(
+                    program,
+                    interface_identifier,
+                    &primitive_struct_members(struct_members.clone())?,
+                )
+                .map_err(|e| anyhow!("Invalid member `{member_identifier}`: {e}"))?;
+                true
+            }
+            _ => matches!(
+                (member_type, operand),
+                (PlaintextType::Literal(LiteralType::U8), SimpleUInt8(_))
+                    | (PlaintextType::Literal(LiteralType::U16), SimpleUInt16(_))
+                    | (PlaintextType::Literal(LiteralType::U32), SimpleUInt32(_))
+                    | (PlaintextType::Literal(LiteralType::U64), SimpleUInt64(_))
+                    | (PlaintextType::Literal(LiteralType::I8), SimpleInt8(_))
+                    | (
+                        PlaintextType::Literal(LiteralType::Boolean),
+                        SimpleBoolean(_)
+                    )
+                    | (PlaintextType::Literal(LiteralType::Field), SimpleField(_))
+                    | (
+                        PlaintextType::Literal(LiteralType::Address),
+                        SimpleAddress(_)
+                    )
+                    | (PlaintextType::Literal(LiteralType::String), SimpleString(_))
+            ),
+        };
+        if !member_matches {
+            bail!("Cast into a struct is not supported for the given types, member `{member_identifier}` is {member_type}");
+        }
+        members.insert(member_identifier.to_string(), operand.clone());
+    }
+    Ok(SimpleStruct(members))
+}
+
+// A lossy cast is not an error but an unsatisfiable constraint, so the keys
+// of a function can be built whatever its inputs are and no proof can be
+// generated for the inputs that make the cast lossy.
+fn enforce_lossless_cast(is_lossless: &Boolean<ConstraintF>) -> Result<()> {
+    is_lossless.enforce_equal(&Boolean::TRUE)?;
+    Ok(())
+}
+
+// Int8Gadget cannot be built from bits so we allocate it with the same
+// allocation mode as the byte and enforce both to be equal.
+fn int8_from_byte(byte: &UInt8Gadget) -> Result<Int8Gadget> {
+    let primitive_value = i8::from_le_bytes([byte.value()?]);
+    let mode = if byte.is_constant() {
+        AllocationMode::Constant
+    } else {
+        AllocationMode::Witness
+    };
+    let value = Int8Gadget::new_variable(byte.cs(), || Ok(primitive_value), mode)?;
+    value
+        .to_bytes()?
+        .as_slice()
+        .enforce_equal(&[byte.clone()])?;
+    Ok(value)
+}
+
 #[cfg(test)]
 mod cast_tests {
-    use super::_cast;
+    use super::{_cast, cast_into_literal, cast_into_struct, cast_operand};
     use crate::{
-        CircuitIOType::{SimpleAddress, SimpleUInt64},
-        ConstraintF,
+        jaleo::Program,
+        CircuitIOType::{
+            self, SimpleAddress, SimpleBoolean, SimpleField, SimpleInt8, SimpleStruct,
+            SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8,
+        },
+        ConstraintF, VMStructMembersMap,
     };
-    use ark_r1cs_std::prelude::AllocVar;
+    use ark_r1cs_std::prelude::{AllocVar, Boolean};
     use ark_relations::r1cs::ConstraintSystem;
     use indexmap::IndexMap;
-    use simpleworks::gadgets::{AddressGadget, UInt64Gadget};
+    use simpleworks::gadgets::{
+        AddressGadget, FieldGadget, Int8Gadget, UInt16Gadget, UInt64Gadget, UInt8Gadget,
+    };
+    use snarkvm::prelude::{EntryType, Identifier, LiteralType, Testnet3};
+    use std::str::FromStr;
+
+    fn sample_operand(operand: CircuitIOType) -> IndexMap<String, CircuitIOType> {
+        let mut operands = IndexMap::new();
+        operands.insert("r0".to_owned(), operand);
+        operands
+    }
 
     fn address<'address>() -> (&'address str, [u8; 63]) {
         let mut address_bytes = [0_u8; 63];
@@ -433,4 +587,310 @@ mod cast_tests {
             "Cast is a two or more operands instruction"
         );
     }
+
+    #[test]
+    fn test_u8_cast_into_u64() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_value = u8::MAX;
+        let operand =
+            SimpleUInt8(UInt8Gadget::new_witness(cs.clone(), || Ok(primitive_value)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::U64).unwrap();
+
+        assert!(matches!(result, SimpleUInt64(_)));
+        assert_eq!(result.value().unwrap(), primitive_value.to_string());
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_u64_cast_into_u16_that_fits() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_value = u64::from(u16::MAX);
+        let operand =
+            SimpleUInt64(UInt64Gadget::new_witness(cs.clone(), || Ok(primitive_value)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::U16).unwrap();
+
+        assert!(matches!(result, SimpleUInt16(_)));
+        assert_eq!(result.value().unwrap(), primitive_value.to_string());
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_u64_cast_into_u8_that_does_not_fit() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_value = u64::from(u8::MAX) + 1;
+        let operand =
+            SimpleUInt64(UInt64Gadget::new_witness(cs.clone(), || Ok(primitive_value)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::U8).unwrap();
+
+        assert!(matches!(result, SimpleUInt8(_)));
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_u16_cast_into_i8() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_value = 127_u16;
+        let operand =
+            SimpleUInt16(UInt16Gadget::new_witness(cs.clone(), || Ok(primitive_value)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::I8).unwrap();
+
+        assert!(matches!(result, SimpleInt8(_)));
+        assert_eq!(result.value().unwrap(), primitive_value.to_string());
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_u8_cast_into_i8_that_does_not_fit() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let operand = SimpleUInt8(UInt8Gadget::new_witness(cs.clone(), || Ok(128_u8)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::I8).unwrap();
+
+        assert!(matches!(result, SimpleInt8(_)));
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_negative_i8_cast_into_u8() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let operand = SimpleInt8(Int8Gadget::new_witness(cs.clone(), || Ok(-1_i8)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::U8).unwrap();
+
+        assert!(matches!(result, SimpleUInt8(_)));
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_negative_i8_cast_into_field() {
+        for primitive_value in [-1_i8, i8::MIN] {
+            let cs = ConstraintSystem::<ConstraintF>::new_ref();
+            let operand =
+                SimpleInt8(Int8Gadget::new_witness(cs.clone(), || Ok(primitive_value)).unwrap());
+
+            let result = cast_into_literal(&sample_operand(operand), &LiteralType::Field).unwrap();
+
+            // snarkVM casts -x into p - x.
+            let expected_value = -ConstraintF::from(primitive_value.unsigned_abs());
+            assert!(matches!(result, SimpleField(_)));
+            assert_eq!(result.value().unwrap(), expected_value.to_string());
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_positive_i8_cast_into_u32() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_value = 100_i8;
+        let operand =
+            SimpleInt8(Int8Gadget::new_witness(cs.clone(), || Ok(primitive_value)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::U32).unwrap();
+
+        assert!(matches!(result, SimpleUInt32(_)));
+        assert_eq!(result.value().unwrap(), primitive_value.to_string());
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_boolean_cast_into_u8() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let operand =
+            SimpleBoolean(Boolean::<ConstraintF>::new_witness(cs.clone(), || Ok(true)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::U8).unwrap();
+
+        assert!(matches!(result, SimpleUInt8(_)));
+        assert_eq!(result.value().unwrap(), "1");
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_u8_cast_into_boolean() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let operand = SimpleUInt8(UInt8Gadget::new_witness(cs.clone(), || Ok(1_u8)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::Boolean).unwrap();
+
+        assert!(matches!(result, SimpleBoolean(_)));
+        assert_eq!(result.value().unwrap(), "true");
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_u8_cast_into_boolean_that_does_not_fit() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let operand = SimpleUInt8(UInt8Gadget::new_witness(cs.clone(), || Ok(2_u8)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::Boolean).unwrap();
+
+        assert!(matches!(result, SimpleBoolean(_)));
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_field_cast_into_u32() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_value = u32::MAX;
+        let operand = SimpleField(
+            FieldGadget::new_witness(cs.clone(), || Ok(ConstraintF::from(primitive_value)))
+                .unwrap(),
+        );
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::U32).unwrap();
+
+        assert!(matches!(result, SimpleUInt32(_)));
+        assert_eq!(result.value().unwrap(), primitive_value.to_string());
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_field_cast_into_u32_that_does_not_fit() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let operand = SimpleField(
+            FieldGadget::new_witness(cs.clone(), || {
+                Ok(ConstraintF::from(u64::from(u32::MAX) + 1))
+            })
+            .unwrap(),
+        );
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::U32).unwrap();
+
+        assert!(matches!(result, SimpleUInt32(_)));
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_u64_cast_into_field() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_value = u64::MAX;
+        let operand =
+            SimpleUInt64(UInt64Gadget::new_witness(cs.clone(), || Ok(primitive_value)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::Field).unwrap();
+
+        assert!(matches!(result, SimpleField(_)));
+        assert_eq!(
+            result.value().unwrap(),
+            ConstraintF::from(primitive_value).to_string()
+        );
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_address_cast_into_u8() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let (_address_str, address_bytes) = address();
+        let operand = SimpleAddress(AddressGadget::new_witness(cs, || Ok(address_bytes)).unwrap());
+
+        let result = cast_into_literal(&sample_operand(operand), &LiteralType::U8).unwrap_err();
+
+        assert_eq!(
+            result.to_string(),
+            "Cast into u8 is not supported for the given type"
+        );
+    }
+
+    #[test]
+    fn test_cast_into_literal_requires_one_operand() {
+        let result = cast_into_literal(&IndexMap::new(), &LiteralType::U8).unwrap_err();
+
+        assert_eq!(result.to_string(), "Cast into u8 requires one operand");
+    }
+
+    #[test]
+    fn test_public_record_entry_is_bound_to_its_operand() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let operand = SimpleUInt64(UInt64Gadget::new_witness(cs.clone(), || Ok(5)).unwrap());
+        let mut aleo_record_entries = IndexMap::new();
+        aleo_record_entries.insert(
+            Identifier::<Testnet3>::from_str("amount").unwrap(),
+            EntryType::<Testnet3>::from_str("u64.public").unwrap(),
+        );
+
+        let program = Program::from_str(
+            "program foo.aleo;\n\nfunction hello:\n    input r0 as u64.public;\n    output r0 as u64.public;\n",
+        )
+        .unwrap();
+
+        let (entry_name, entry) =
+            cast_operand(&program, "r1", 2, &operand, &aleo_record_entries, &cs).unwrap();
+
+        assert_eq!(entry_name, "amount");
+        assert_eq!(entry.value().unwrap(), "5");
+        // The entry only becomes a public input if the record is an output.
+        assert!(entry.is_witness().unwrap());
+        assert_eq!(cs.num_instance_variables(), 1);
+        assert!(cs.is_satisfied().unwrap());
+
+        // Flipping the most significant bit of the entry must not satisfy
+        // the circuit for the same operand.
+        if let Some(mut cs) = cs.borrow_mut() {
+            let entry_bit = cs.witness_assignment.last_mut().unwrap();
+            *entry_bit = ConstraintF::from(1_u8) - *entry_bit;
+        }
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_public_owner_and_gates_are_bound_to_their_operands() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let (_primitive_address_str, primitive_address_bytes) = address();
+        let mut operands = IndexMap::new();
+        operands.insert(
+            "owner".to_owned(),
+            SimpleAddress(
+                AddressGadget::new_input(cs.clone(), || Ok(primitive_address_bytes)).unwrap(),
+            ),
+        );
+        operands.insert(
+            "gates".to_owned(),
+            SimpleUInt64(UInt64Gadget::new_input(cs.clone(), || Ok(1)).unwrap()),
+        );
+
+        _cast(operands, cs.clone()).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+
+        // Flipping the most significant bit of the gates must not satisfy the
+        // circuit for the same operands.
+        if let Some(mut cs) = cs.borrow_mut() {
+            let gates_bit = cs.witness_assignment.last_mut().unwrap();
+            *gates_bit = ConstraintF::from(1_u8) - *gates_bit;
+        }
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_cast_into_struct_checks_nested_structs() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let program = Program::from_str(
+            "program foo.aleo;\n\ninterface point:\n    x as u8;\n    y as u8;\n\ninterface segment:\n    start as point;\n    length as u8;\n\nfunction hello:\n    input r0 as u8.public;\n    output r0 as u8.public;\n",
+        )
+        .unwrap();
+        let segment = program
+            .get_interface(&Identifier::from_str("segment").unwrap())
+            .unwrap();
+        let byte = || SimpleUInt8(UInt8Gadget::new_witness(cs.clone(), || Ok(1_u8)).unwrap());
+
+        let mut point = VMStructMembersMap::new();
+        point.insert("x".to_owned(), byte());
+        point.insert("y".to_owned(), byte());
+        let mut operands = IndexMap::new();
+        operands.insert("r0".to_owned(), SimpleStruct(point));
+        operands.insert("r1".to_owned(), byte());
+        let result = cast_into_struct(&program, &operands, segment.members()).unwrap();
+        assert!(matches!(result, SimpleStruct(_)));
+
+        // A struct with other members is not a point.
+        let mut not_a_point = VMStructMembersMap::new();
+        not_a_point.insert("a".to_owned(), byte());
+        operands.insert("r0".to_owned(), SimpleStruct(not_a_point));
+        let error = cast_into_struct(&program, &operands, segment.members()).unwrap_err();
+        assert!(error.to_string().contains("Invalid member `start`"));
+    }
 }
@@ -1,13 +1,20 @@
-use crate::circuit_io_type::CircuitIOType;
+use crate::{circuit_io_type::CircuitIOType, poseidon_gadget::poseidon_hash_bytes};
 use anyhow::{bail, Result};
 use ark_r1cs_std::{alloc::AllocVar, R1CSVar, ToBytesGadget};
 use indexmap::IndexMap;
 use simpleworks::{gadgets::FieldGadget, hash};
 pub use CircuitIOType::{
-    SimpleAddress, SimpleField, SimpleInt8, SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8,
+    SimpleAddress, SimpleField, SimpleInt8, SimpleString, SimpleUInt16, SimpleUInt32, SimpleUInt64,
+    SimpleUInt8,
 };
 
-// TODO: Generate constraints. Use the Poseidon hash gadget.
+// TODO: Generate constraints for the other types. Use the Poseidon hash gadget.
+/// Hashes the operand with Poseidon. Strings are hashed inside the circuit,
+/// with their length and all their padding bytes, so every string is hashed
+/// the same way, while the other types are still hashed outside of it. Note
+/// that the Poseidon instance is not snarkVM's, so the hashes differ from the
+/// ones Aleo computes, and that the other `hash.*` instructions are not
+/// supported.
 pub fn hash_psd2(operands: &IndexMap<String, CircuitIOType>) -> Result<CircuitIOType> {
     match operands
         .values()
@@ -62,6 +69,7 @@ pub fn hash_psd2(operands: &IndexMap<String, CircuitIOType>) -> Result<CircuitIO
                 Ok(output)
             })?))
         }
+        [SimpleString(string)] => Ok(SimpleField(poseidon_hash_bytes(&string.to_bytes()?)?)),
         [_] => bail!("hash.psd2 is not supported for the given type"),
         [..] => bail!("hash.psd2 requires one operand"),
     }
@@ -80,9 +88,10 @@ mod hash_psd2_unit_tests {
     use crate::{
         instructions::hash_psd2::hash_psd2,
         CircuitIOType::{
-            self, SimpleAddress, SimpleBoolean, SimpleInt8, SimpleUInt16, SimpleUInt32,
-            SimpleUInt64, SimpleUInt8,
+            self, SimpleAddress, SimpleBoolean, SimpleInt8, SimpleString, SimpleUInt16,
+            SimpleUInt32, SimpleUInt64, SimpleUInt8,
         },
+        StringGadget,
     };
 
     fn sample_hash_operands(operand: CircuitIOType) -> IndexMap<String, CircuitIOType> {
@@ -187,6 +196,54 @@ mod hash_psd2_unit_tests {
         // assert_ne!(output.value().unwrap(), input.value().unwrap())
     }
 
+    #[test]
+    fn test_string_hash_psd2() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_input_value = "hello".to_owned();
+
+        let input = SimpleString(
+            StringGadget::new_witness(cs.clone(), || Ok(primitive_input_value)).unwrap(),
+        );
+
+        let output = hash_psd2(&sample_hash_operands(input.clone())).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+        assert!(matches!(output, CircuitIOType::SimpleField(_)));
+        assert_ne!(output.value().unwrap(), input.value().unwrap())
+    }
+
+    #[test]
+    fn test_string_hash_psd2_is_constrained() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let input =
+            SimpleString(StringGadget::new_witness(cs.clone(), || Ok("hello".to_owned())).unwrap());
+        let same_input =
+            SimpleString(StringGadget::new_constant(cs.clone(), "hello".to_owned()).unwrap());
+        let other_input =
+            SimpleString(StringGadget::new_constant(cs.clone(), "hellp".to_owned()).unwrap());
+        let constraints_before = cs.num_constraints();
+
+        let output = hash_psd2(&sample_hash_operands(input)).unwrap();
+
+        assert!(cs.num_constraints() > constraints_before);
+        assert!(!output.is_constant());
+        assert_eq!(
+            output.value().unwrap(),
+            hash_psd2(&sample_hash_operands(same_input))
+                .unwrap()
+                .value()
+                .unwrap()
+        );
+        assert_ne!(
+            output.value().unwrap(),
+            hash_psd2(&sample_hash_operands(other_input))
+                .unwrap()
+                .value()
+                .unwrap()
+        );
+        assert!(cs.is_satisfied().unwrap());
+    }
+
     #[test]
     fn test_hash_psd2_with_no_operands() {
         let result = hash_psd2(&IndexMap::new()).unwrap_err();
@@ -3,7 +3,7 @@ use anyhow::{bail, Result};
 use ark_r1cs_std::prelude::EqGadget;
 use indexmap::IndexMap;
 pub use CircuitIOType::{
-    SimpleBoolean, SimpleInt8, SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8,
+    SimpleBoolean, SimpleInt8, SimpleString, SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8,
 };
 
 pub fn is_eq(operands: &IndexMap<String, CircuitIOType>) -> Result<CircuitIOType> {
@@ -27,6 +27,9 @@ pub fn is_eq(operands: &IndexMap<String, CircuitIOType>) -> Result<CircuitIOType
         [SimpleInt8(left_operand), SimpleInt8(right_operand)] => {
             Ok(SimpleBoolean(right_operand.is_eq(left_operand)?))
         }
+        [SimpleString(left_operand), SimpleString(right_operand)] => {
+            Ok(SimpleBoolean(right_operand.is_eq(left_operand)?))
+        }
         [_, _] => bail!("is.eq is not supported for the given types"),
         [..] => bail!("is.eq requires two operands"),
     }
@@ -53,6 +56,9 @@ pub fn is_neq(operands: &IndexMap<String, CircuitIOType>) -> Result<CircuitIOTyp
         [SimpleInt8(left_operand), SimpleInt8(right_operand)] => {
             Ok(SimpleBoolean(right_operand.is_neq(left_operand)?))
         }
+        [SimpleString(left_operand), SimpleString(right_operand)] => {
+            Ok(SimpleBoolean(right_operand.is_neq(left_operand)?))
+        }
         [_, _] => bail!("is.neq is not supported for the given types"),
         [..] => bail!("is.neq requires two operands"),
     }
@@ -64,9 +70,10 @@ mod equality_tests {
     use crate::{
         instructions::is_eq::is_neq,
         CircuitIOType::{
-            SimpleAddress, SimpleBoolean, SimpleInt8, SimpleUInt16, SimpleUInt32, SimpleUInt64,
-            SimpleUInt8,
+            SimpleAddress, SimpleBoolean, SimpleInt8, SimpleString, SimpleUInt16, SimpleUInt32,
+            SimpleUInt64, SimpleUInt8,
         },
+        StringGadget,
     };
     use anyhow::Result;
     use ark_r1cs_std::prelude::{AllocVar, Boolean};
@@ -299,6 +306,39 @@ mod equality_tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_string_is_eq_is_true() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_left_operand = "hello".to_owned();
+        let primitive_right_operand = "hello".to_owned();
+
+        let left_operand = SimpleString(
+            StringGadget::new_witness(cs.clone(), || Ok(primitive_left_operand)).unwrap(),
+        );
+        let right_operand =
+            SimpleString(StringGadget::new_constant(cs.clone(), primitive_right_operand).unwrap());
+
+        assert_equality_instructions(&sample_operands(left_operand, right_operand), cs, true)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_string_is_eq_is_false() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_left_operand = "hello".to_owned();
+        let primitive_right_operand = "hello!".to_owned();
+
+        let left_operand = SimpleString(
+            StringGadget::new_witness(cs.clone(), || Ok(primitive_left_operand)).unwrap(),
+        );
+        let right_operand = SimpleString(
+            StringGadget::new_witness(cs.clone(), || Ok(primitive_right_operand)).unwrap(),
+        );
+
+        assert_equality_instructions(&sample_operands(left_operand, right_operand), cs, false)
+            .unwrap();
+    }
+
     #[test]
     fn test_is_eq_with_more_than_two_operands() {
         let cs = ConstraintSystem::<ConstraintF>::new_ref();
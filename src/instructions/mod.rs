@@ -7,7 +7,10 @@ mod and;
 pub use and::and;
 
 mod cast;
+pub(crate) use cast::allocate_entry;
 pub use cast::cast;
+pub use cast::cast_into_literal;
+pub use cast::cast_into_struct;
 
 mod div;
 pub use div::div;
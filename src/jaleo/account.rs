@@ -0,0 +1,181 @@
+use super::{Address, PrivateKey, ViewKey};
+use crate::universal_srs::lambdavm_dir;
+use anyhow::{anyhow, Result};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::{
+    fs,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// An account is a private key along with the view key and address derived
+/// from it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Account {
+    pub private_key: PrivateKey,
+    pub view_key: ViewKey,
+    pub address: Address,
+}
+
+impl Account {
+    /// Generates a new account. If a seed is given the account is always the
+    /// same for it, which is handy for tests. Seeded accounts use ChaCha20,
+    /// whose output is stable across versions of `rand`.
+    pub fn new(seed: Option<u64>) -> Result<Self> {
+        let private_key = match seed {
+            Some(seed) => PrivateKey::new(&mut ChaCha20Rng::seed_from_u64(seed))?,
+            None => PrivateKey::new(&mut rand::thread_rng())?,
+        };
+        Self::from_private_key(private_key)
+    }
+
+    pub fn from_private_key(private_key: PrivateKey) -> Result<Self> {
+        Ok(Self {
+            private_key,
+            view_key: ViewKey::try_from(private_key)?,
+            address: Address::try_from(private_key)?,
+        })
+    }
+
+    /// Path of the account file, which lives in the lambdavm directory
+    /// (`~/.lambdavm`).
+    pub fn filepath() -> Result<PathBuf> {
+        Ok(lambdavm_dir()?.join("account.json"))
+    }
+
+    /// Writes the account to the account file. An existing account is only
+    /// overwritten if `force` is set, since its private key would be lost.
+    pub fn write_to_file(&self, force: bool) -> Result<PathBuf> {
+        let file_path = Self::filepath()?;
+        self.write_to_path(&file_path, force)?;
+        Ok(file_path)
+    }
+
+    /// Same as `write_to_file` but for any path. The file is only readable
+    /// and writable by its owner.
+    pub fn write_to_path(&self, file_path: &Path, force: bool) -> Result<()> {
+        if let Some(account_dir) = file_path.parent() {
+            fs::create_dir_all(account_dir)?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true);
+        if force {
+            options.create(true).truncate(true);
+        } else {
+            options.create_new(true);
+        }
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(file_path).map_err(|e| match e.kind() {
+            ErrorKind::AlreadyExists => anyhow!(
+                "An account is already stored in {}, use --force to overwrite it",
+                file_path.display()
+            ),
+            _ => anyhow!("Error writing account file {}: {e}", file_path.display()),
+        })?;
+        // The mode is only applied to new files.
+        #[cfg(unix)]
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn load_from_file() -> Result<Self> {
+        let file_path = Self::filepath()?;
+        let serialized_account = fs::read_to_string(&file_path).map_err(|e| {
+            anyhow!(
+                "Error reading account file {}: {e}. Create an account first",
+                file_path.display()
+            )
+        })?;
+        Ok(serde_json::from_str(&serialized_account)?)
+    }
+}
+
+impl FromStr for Account {
+    type Err = anyhow::Error;
+
+    /// Imports an account from its private key.
+    fn from_str(private_key: &str) -> Result<Self> {
+        Self::from_private_key(PrivateKey::from_str(private_key.trim())?)
+    }
+}
+
+impl std::fmt::Display for Account {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Private key: {}", self.private_key)?;
+        writeln!(f, "   View key: {}", self.view_key)?;
+        write!(f, "    Address: {}", self.address)
+    }
+}
+
+#[cfg(test)]
+mod account_tests {
+    use super::Account;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_seeded_accounts_are_deterministic() {
+        let account = Account::new(Some(42)).unwrap();
+        let other_account = Account::new(Some(42)).unwrap();
+        let different_account = Account::new(Some(43)).unwrap();
+
+        assert_eq!(account, other_account);
+        assert_ne!(account, different_account);
+    }
+
+    #[test]
+    fn test_import_account() {
+        let account = Account::new(None).unwrap();
+
+        let imported_account = Account::from_str(&account.private_key.to_string()).unwrap();
+
+        assert_eq!(account, imported_account);
+    }
+
+    #[test]
+    fn test_import_invalid_private_key() {
+        assert!(Account::from_str("APrivateKey1invalid").is_err());
+    }
+
+    #[test]
+    fn test_serialize_account() {
+        let account = Account::new(Some(1)).unwrap();
+
+        let serialized_account = serde_json::to_string(&account).unwrap();
+        let deserialized_account: Account = serde_json::from_str(&serialized_account).unwrap();
+
+        assert_eq!(account, deserialized_account);
+    }
+
+    #[test]
+    fn test_write_account_does_not_overwrite() {
+        let account = Account::new(Some(1)).unwrap();
+        let other_account = Account::new(Some(2)).unwrap();
+        let file_path = std::env::temp_dir().join("lambdavm_account_test.json");
+        let _ = std::fs::remove_file(&file_path);
+
+        account.write_to_path(&file_path, false).unwrap();
+        assert!(other_account.write_to_path(&file_path, false).is_err());
+        let stored_account: Account =
+            serde_json::from_str(&std::fs::read_to_string(&file_path).unwrap()).unwrap();
+        assert_eq!(stored_account, account);
+
+        other_account.write_to_path(&file_path, true).unwrap();
+        let stored_account: Account =
+            serde_json::from_str(&std::fs::read_to_string(&file_path).unwrap()).unwrap();
+        assert_eq!(stored_account, other_account);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&file_path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
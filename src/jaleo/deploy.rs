@@ -1,6 +1,9 @@
 use super::{Identifier, Program};
-use crate::build_program;
-use anyhow::Result;
+use crate::{
+    binary::{self, Kind},
+    build_program_with_imports,
+};
+use anyhow::{anyhow, ensure, Result};
 use indexmap::IndexMap;
 use serde::{
     de,
@@ -11,7 +14,13 @@ use simpleworks::{
     marlin::serialization::{deserialize_verifying_key, serialize_verifying_key},
     marlin::VerifyingKey,
 };
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    fs,
+    io::{Read, Write},
+    path::Path,
+    str::FromStr,
+};
 
 #[derive(Clone)]
 pub struct VerifyingKeyMap {
@@ -24,6 +33,50 @@ pub struct Deployment {
     pub verifying_keys: VerifyingKeyMap,
 }
 
+impl Deployment {
+    /// Reads a deployment in either the JSON or the binary format.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        if binary::is_binary_file(path)? {
+            return Self::read_binary(&mut binary::open_file(path)?);
+        }
+        let serialized_deployment = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Error reading deployment {}: {e}", path.display()))?;
+        Ok(serde_json::from_str(&serialized_deployment)?)
+    }
+
+    pub fn write_to_binary_file(&self, path: &Path) -> Result<()> {
+        let mut writer = binary::create_file(path)?;
+        self.write_binary(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn write_binary<W: Write>(&self, writer: &mut W) -> Result<()> {
+        binary::write_header(writer, Kind::Deployment)?;
+        binary::write_string(writer, &self.program.to_string())?;
+        binary::write_length(writer, self.verifying_keys.map.len())?;
+        for (function_name, verifying_key) in &self.verifying_keys.map {
+            binary::write_string(writer, &function_name.to_string())?;
+            binary::write_canonical(writer, verifying_key)?;
+        }
+        Ok(())
+    }
+
+    pub fn read_binary<R: Read>(reader: &mut R) -> Result<Self> {
+        binary::read_header(reader, Kind::Deployment)?;
+        let program = Program::from_str(&binary::read_string(reader)?)?;
+        let mut map = IndexMap::new();
+        for _ in 0..binary::read_length(reader)? {
+            let function_name = Identifier::from_str(&binary::read_string(reader)?)?;
+            map.insert(function_name, binary::read_canonical(reader)?);
+        }
+        Ok(Self {
+            program,
+            verifying_keys: VerifyingKeyMap { map },
+        })
+    }
+}
+
 impl Serialize for VerifyingKeyMap {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -75,10 +128,19 @@ impl Debug for VerifyingKeyMap {
 
 // these struct-level functions should probably not be in the Vm level
 pub fn generate_deployment(program_string: &str) -> Result<Deployment> {
+    generate_deployment_with_imports(program_string, &[])
+}
+
+/// Same as `generate_deployment` but for programs that import others. The
+/// imported programs are only used to build the keys, they are not deployed.
+pub fn generate_deployment_with_imports(
+    program_string: &str,
+    imports: &[Program],
+) -> Result<Deployment> {
     // NOTE: we're skipping the part of imported programs
     // https://github.com/Entropy1729/snarkVM/blob/2c4e282df46ed71c809fd4b49738fd78562354ac/vm/package/deploy.rs#L149
 
-    let (program, program_build) = build_program(program_string)?;
+    let (program, program_build) = build_program_with_imports(program_string, imports)?;
     let verifying_keys: IndexMap<Identifier, VerifyingKey> = program_build
         .map
         .into_iter()
@@ -92,3 +154,35 @@ pub fn generate_deployment(program_string: &str) -> Result<Deployment> {
         },
     })
 }
+
+/// Checks that a deployment has exactly one verifying key per function of
+/// its program, and that each of them is the one the program builds to.
+pub fn check_deployment(deployment: &Deployment) -> Result<()> {
+    check_deployment_with_imports(deployment, &[])
+}
+
+/// Same as `check_deployment` but for programs that import others.
+pub fn check_deployment_with_imports(deployment: &Deployment, imports: &[Program]) -> Result<()> {
+    let program = &deployment.program;
+    let verifying_keys = &deployment.verifying_keys.map;
+    for function_name in verifying_keys.keys() {
+        ensure!(
+            program.contains_function(function_name),
+            "Deployment has a verifying key for {function_name}, which is not a function of {}",
+            program.id()
+        );
+    }
+
+    let (_program, program_build) = build_program_with_imports(&program.to_string(), imports)?;
+    for (function_name, (_proving_key, expected_verifying_key)) in program_build.map {
+        let verifying_key = verifying_keys.get(&function_name).ok_or_else(|| {
+            anyhow!("Deployment is missing the verifying key for {function_name}")
+        })?;
+        ensure!(
+            serialize_verifying_key(verifying_key.clone())?
+                == serialize_verifying_key(expected_verifying_key)?,
+            "Verifying key for {function_name} does not match the program"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use super::{Proof, VerifyingKey};
+use anyhow::{anyhow, bail, ensure, Result};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use bech32::{FromBase32, ToBase32, Variant};
+use std::{fmt::Display, str::FromStr};
+
+/// Prefix of bech32m encoded proofs, as in snarkVM.
+pub const PROOF_PREFIX: &str = "proof";
+/// Prefix of bech32m encoded verifying keys, as in snarkVM.
+pub const VERIFYING_KEY_PREFIX: &str = "verifier";
+/// Prefix of bech32m encoded records, as in snarkVM.
+pub const RECORD_PREFIX: &str = "record";
+
+/// Encodes bytes as bech32m with the given human readable prefix.
+pub(crate) fn encode_bech32m(prefix: &str, bytes: &[u8]) -> Result<String> {
+    bech32::encode(prefix, bytes.to_base32(), Variant::Bech32m)
+        .map_err(|e| anyhow!("Error encoding {prefix}: {e}"))
+}
+
+/// Decodes a bech32m string, checking its prefix and checksum, so mistyped
+/// or truncated strings are rejected.
+pub(crate) fn decode_bech32m(prefix: &str, encoded: &str) -> Result<Vec<u8>> {
+    let (decoded_prefix, data, variant) = bech32::decode(encoded.trim())
+        .map_err(|e| anyhow!("Invalid {prefix}, it may be mistyped or truncated: {e}"))?;
+    ensure!(
+        decoded_prefix == prefix,
+        "Expected a {prefix} but got a {decoded_prefix}"
+    );
+    if variant != Variant::Bech32m {
+        bail!("Invalid {prefix}: expected a bech32m encoding");
+    }
+    Ok(Vec::<u8>::from_base32(&data)?)
+}
+
+fn encode_canonical(
+    prefix: &str,
+    value: &impl CanonicalSerialize,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    let mut bytes = Vec::new();
+    value.serialize(&mut bytes).map_err(|_e| std::fmt::Error)?;
+    let encoded = encode_bech32m(prefix, &bytes).map_err(|_e| std::fmt::Error)?;
+    write!(f, "{encoded}")
+}
+
+fn decode_canonical<T: CanonicalDeserialize>(prefix: &str, encoded: &str) -> Result<T> {
+    let bytes = decode_bech32m(prefix, encoded)?;
+    T::deserialize(bytes.as_slice()).map_err(|e| anyhow!("Invalid {prefix}: {e}"))
+}
+
+/// A proof that displays as and parses from `proof1...`.
+#[derive(Clone)]
+pub struct EncodedProof(pub Proof);
+
+impl Display for EncodedProof {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        encode_canonical(PROOF_PREFIX, &self.0, f)
+    }
+}
+
+impl FromStr for EncodedProof {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self(decode_canonical(PROOF_PREFIX, s)?))
+    }
+}
+
+/// A verifying key that displays as and parses from `verifier1...`.
+#[derive(Clone)]
+pub struct EncodedVerifyingKey(pub VerifyingKey);
+
+impl Display for EncodedVerifyingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        encode_canonical(VERIFYING_KEY_PREFIX, &self.0, f)
+    }
+}
+
+impl FromStr for EncodedVerifyingKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self(decode_canonical(VERIFYING_KEY_PREFIX, s)?))
+    }
+}
+
+#[cfg(test)]
+mod encoding_tests {
+    use super::{decode_bech32m, encode_bech32m, PROOF_PREFIX, RECORD_PREFIX};
+
+    #[test]
+    fn test_bech32m_round_trip() {
+        let bytes = (0..=255_u8).collect::<Vec<u8>>();
+        let encoded = encode_bech32m(RECORD_PREFIX, &bytes).unwrap();
+
+        assert!(encoded.starts_with("record1"));
+        assert_eq!(decode_bech32m(RECORD_PREFIX, &encoded).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_bech32m_detects_mistakes() {
+        let encoded = encode_bech32m(RECORD_PREFIX, &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+
+        let mut truncated = encoded.clone();
+        truncated.pop();
+        assert!(decode_bech32m(RECORD_PREFIX, &truncated).is_err());
+
+        let mut mistyped = encoded.clone();
+        let last = mistyped.pop().unwrap();
+        mistyped.push(if last == 'q' { 'p' } else { 'q' });
+        assert!(decode_bech32m(RECORD_PREFIX, &mistyped).is_err());
+
+        assert!(decode_bech32m(PROOF_PREFIX, &encoded).is_err());
+    }
+
+    #[test]
+    fn test_bech32_variant_is_rejected() {
+        let encoded = bech32::encode(
+            RECORD_PREFIX,
+            bech32::ToBase32::to_base32(&[1_u8, 2, 3]),
+            bech32::Variant::Bech32,
+        )
+        .unwrap();
+
+        assert!(decode_bech32m(RECORD_PREFIX, &encoded).is_err());
+    }
+}
@@ -1,20 +1,25 @@
 use super::{credits, Function, Identifier, PrivateKey, Program, Transition};
 use crate::{
-    helpers::to_address,
-    jaleo::{program_is_coinbase, Record, UserInputValueType},
+    helpers::{self, to_address},
+    jaleo::{
+        program_is_coinbase, EncryptionScheme, Record, RecordEntriesMap, RecordEntry,
+        StructMembersMap, UserInputValueType, Visibility,
+    },
     variable_type::VariableType,
-    CircuitInputType, CircuitOutputType, SimpleFunctionVariables,
+    CircuitIOType, CircuitInputType, CircuitOutputType, SimpleFunctionVariables,
+    VMRecordEntriesMap, VMStructMembersMap,
 };
 use anyhow::{anyhow, bail, ensure, Result};
 use ark_r1cs_std::R1CSVar;
 use indexmap::IndexMap;
 use log::debug;
 use simpleworks::marlin::serialization::serialize_proof;
-use snarkvm::prelude::{Scalar, Uniform};
+use snarkvm::prelude::{EntryType, Scalar, Testnet3, Uniform, ValueType};
+use std::str::FromStr;
 
 use crate::CircuitIOType::{
-    SimpleAddress, SimpleBoolean, SimpleField, SimpleInt8, SimpleRecord, SimpleUInt16,
-    SimpleUInt32, SimpleUInt64, SimpleUInt8,
+    SimpleAddress, SimpleBoolean, SimpleField, SimpleInt8, SimpleRecord, SimpleString,
+    SimpleStruct, SimpleUInt16, SimpleUInt32, SimpleUInt64, SimpleUInt8,
 };
 
 pub fn credits_execution(
@@ -30,6 +35,27 @@ pub fn execution(
     function_name: &Identifier,
     inputs: &[UserInputValueType],
     private_key: &PrivateKey,
+) -> Result<Vec<Transition>> {
+    execution_with_imports(
+        program,
+        &[],
+        function_name,
+        inputs,
+        private_key,
+        EncryptionScheme::default(),
+    )
+}
+
+/// Same as `execution` but for programs that import others. Input records of
+/// imported programs get their serial numbers like local ones. Output records
+/// are encrypted with the given scheme.
+pub fn execution_with_imports(
+    program: &Program,
+    imports: &[Program],
+    function_name: &Identifier,
+    inputs: &[UserInputValueType],
+    private_key: &PrivateKey,
+    scheme: EncryptionScheme,
 ) -> Result<Vec<Transition>> {
     ensure!(
         !program_is_coinbase(&program.id().to_string(), &function_name.to_string()),
@@ -45,11 +71,28 @@ pub fn execution(
         .get_function(function_name)
         .map_err(|e| anyhow!("{}", e))?;
 
-    let (compiled_function_variables, proof) =
-        crate::execute_function(program, &function.to_string(), inputs)?;
+    let (compiled_function_variables, proof, _constraint_counts) =
+        crate::execute_function_with_imports(
+            program,
+            imports,
+            &function.name().to_string(),
+            inputs,
+        )?;
 
-    let inputs = process_circuit_inputs(&function, &compiled_function_variables, private_key)?;
-    let outputs = process_circuit_outputs(&function, &compiled_function_variables)?;
+    let inputs = process_circuit_inputs(
+        program,
+        imports,
+        &function,
+        &compiled_function_variables,
+        private_key,
+    )?;
+    let outputs = process_circuit_outputs(
+        program,
+        imports,
+        &function,
+        &compiled_function_variables,
+        scheme,
+    )?;
 
     let bytes_proof = serialize_proof(proof)?;
     let encoded_proof = hex::encode(bytes_proof);
@@ -69,6 +112,8 @@ pub fn execution(
 /// Returns a hash map with the circuit inputs of a given function and its variables.
 ///
 /// # Parameters
+/// - `program` - program the function belongs to.
+/// - `imports` - programs imported by `program`, where external records are defined.
 /// - `function` - function to be analyzed.
 /// - `program_variables` - variables of the function.
 ///  
@@ -76,6 +121,8 @@ pub fn execution(
 /// - `IndexMap` of the Circuit Output.
 ///
 pub fn process_circuit_inputs(
+    program: &Program,
+    imports: &[Program],
     function: &Function,
     program_variables: &SimpleFunctionVariables,
     private_key: &PrivateKey,
@@ -92,7 +139,9 @@ pub fn process_circuit_inputs(
             })?;
 
         circuit_inputs.insert(register, {
-            if program_variable.is_witness()? {
+            if let ValueType::Constant(_) = o.value_type() {
+                VariableType::Constant(primitive_value(program_variable)?)
+            } else if program_variable.is_witness()? {
                 match program_variable {
                     SimpleUInt8(v) => VariableType::Private(UserInputValueType::U8(v.value()?)),
                     SimpleInt8(v) => VariableType::Private(UserInputValueType::I8(v.value()?)),
@@ -101,23 +150,9 @@ pub fn process_circuit_inputs(
                     SimpleUInt64(v) => VariableType::Private(UserInputValueType::U64(v.value()?)),
                     SimpleRecord(r) => {
                         // VMRecord to JAleoRecord
-                        let mut primitive_entries = IndexMap::new();
-                        for (k, v) in r.entries {
-                            let primitive_value = match v {
-                                SimpleUInt8(v) => UserInputValueType::U8(v.value()?),
-                                SimpleInt8(v) => UserInputValueType::I8(v.value()?),
-                                SimpleUInt16(v) => UserInputValueType::U16(v.value()?),
-                                SimpleUInt32(v) => UserInputValueType::U32(v.value()?),
-                                SimpleUInt64(v) => UserInputValueType::U64(v.value()?),
-                                SimpleRecord(_) => bail!("Nested records are not supported"),
-                                SimpleAddress(v) => {
-                                    UserInputValueType::Address(to_address(v.value()?))
-                                }
-                                SimpleBoolean(b) => UserInputValueType::Boolean(b.value()?),
-                                SimpleField(f) => UserInputValueType::Field(f.value()?),
-                            };
-                            primitive_entries.insert(k, primitive_value);
-                        }
+                        let entry_types =
+                            helpers::record_entry_types(program, imports, o.value_type())?;
+                        let primitive_entries = primitive_record_entries(r.entries, &entry_types)?;
                         let record = Record::new(
                             to_address(r.owner.value()?),
                             r.gates.value()?,
@@ -139,6 +174,12 @@ pub fn process_circuit_inputs(
                         VariableType::Private(UserInputValueType::Boolean(b.value()?))
                     }
                     SimpleField(f) => VariableType::Private(UserInputValueType::Field(f.value()?)),
+                    SimpleStruct(members) => VariableType::Private(UserInputValueType::Struct(
+                        primitive_struct_members(members)?,
+                    )),
+                    SimpleString(s) => {
+                        VariableType::Private(UserInputValueType::String(s.value()?))
+                    }
                 }
             } else {
                 match program_variable {
@@ -161,6 +202,10 @@ pub fn process_circuit_inputs(
                         VariableType::Public(UserInputValueType::Boolean(b.value()?))
                     }
                     SimpleField(f) => VariableType::Public(UserInputValueType::Field(f.value()?)),
+                    SimpleStruct(members) => VariableType::Public(UserInputValueType::Struct(
+                        primitive_struct_members(members)?,
+                    )),
+                    SimpleString(s) => VariableType::Public(UserInputValueType::String(s.value()?)),
                 }
             }
         });
@@ -172,61 +217,33 @@ pub fn process_circuit_inputs(
 /// Returns a hash map with the circuit outputs of a given function and its variables.
 ///
 /// # Parameters
+/// - `program` - program the function belongs to.
+/// - `imports` - programs imported by `program`, where external records are defined.
 /// - `function` - function to be analyzed.
 /// - `program_variables` - variables of the function.
+/// - `scheme` - scheme the output records are encrypted with.
 ///  
 /// # Returns
 /// - `IndexMap` of the Circuit Output.
 ///
 pub fn process_circuit_outputs(
+    program: &Program,
+    imports: &[Program],
     function: &Function,
     program_variables: &SimpleFunctionVariables,
+    scheme: EncryptionScheme,
 ) -> Result<CircuitOutputType> {
     let mut circuit_outputs = IndexMap::new();
     function.outputs().iter().try_for_each(|o| {
         let register_identifier = o.register().to_string();
-        // output can be of the form 'r7.owner', so split and get the first section
-        let register_split: Vec<&str> = register_identifier.split('.').collect();
-
-        ensure!(
-            register_split.len() <= 2,
-            "Output field {register_identifier} was not specified correctly"
-        );
-
-        let register_variable = match register_split.first() {
-            Some(register_variable) => register_variable,
-            None => {
-                return Err(anyhow!(
-                    "Could not get the variable in Output field: {register_identifier}"
-                ))
-            }
-        };
-        let program_variable = program_variables
-            .get(*register_variable)
-            .ok_or_else(|| anyhow!("Register \"{register_variable}\" not found"))
-            .and_then(|r| {
-                // if desired output is a record field (ie `output r0.gates as u64.public`),
-                // get the field; get the whole register otherwise
-                let register_value = match (r, register_split.len() == 2) {
-                    (Some(SimpleRecord(record)), true) => {
-                        if let Some(key) = register_split.get(1) {
-                            match *key {
-                                "owner" => Some(SimpleAddress(record.owner.clone())),
-                                "gates" => Some(SimpleUInt64(record.gates.clone())),
-                                _ => record.entries.get(*key).cloned(),
-                            }
-                        } else {
-                            None
-                        }
-                    }
-                    _ => r.clone(),
-                };
-                register_value
-                    .ok_or_else(|| anyhow!("Register \"{register_identifier}\" not assigned"))
-            })?;
+        // output can be of the form 'r7.owner' or 'r7.point.x', so get the
+        // whole register first and then the member if needed
+        let program_variable = helpers::register_value(program_variables, o.register())?;
 
         circuit_outputs.insert(register_identifier, {
-            if program_variable.is_witness()? {
+            if let ValueType::Constant(_) = o.value_type() {
+                VariableType::Constant(primitive_value(program_variable)?)
+            } else if program_variable.is_witness()? {
                 match program_variable {
                     SimpleUInt8(v) => VariableType::Private(UserInputValueType::U8(v.value()?)),
                     SimpleInt8(v) => VariableType::Private(UserInputValueType::I8(v.value()?)),
@@ -235,23 +252,9 @@ pub fn process_circuit_outputs(
                     SimpleUInt64(v) => VariableType::Private(UserInputValueType::U64(v.value()?)),
                     SimpleRecord(r) => {
                         // VMRecord to JAleoRecord
-                        let mut primitive_entries = IndexMap::new();
-                        for (k, v) in r.entries {
-                            let primitive_value = match v {
-                                SimpleUInt8(v) => UserInputValueType::U8(v.value()?),
-                                SimpleInt8(v) => UserInputValueType::I8(v.value()?),
-                                SimpleUInt16(v) => UserInputValueType::U16(v.value()?),
-                                SimpleUInt32(v) => UserInputValueType::U32(v.value()?),
-                                SimpleUInt64(v) => UserInputValueType::U64(v.value()?),
-                                SimpleRecord(_) => bail!("Nested records are not supported"),
-                                SimpleAddress(v) => {
-                                    UserInputValueType::Address(to_address(v.value()?))
-                                }
-                                SimpleBoolean(b) => UserInputValueType::Boolean(b.value()?),
-                                SimpleField(f) => UserInputValueType::Field(f.value()?),
-                            };
-                            primitive_entries.insert(k, primitive_value);
-                        }
+                        let entry_types =
+                            helpers::record_entry_types(program, imports, o.value_type())?;
+                        let primitive_entries = primitive_record_entries(r.entries, &entry_types)?;
                         let mut record = Record::new(
                             to_address(r.owner.value()?),
                             r.gates.value()?,
@@ -261,7 +264,7 @@ pub fn process_circuit_outputs(
                         let rng = &mut rand::thread_rng();
                         let randomizer = Scalar::rand(rng);
 
-                        let encrypted_record = record.encrypt(randomizer)?;
+                        let encrypted_record = record.encrypt_with_scheme(randomizer, scheme)?;
                         // NOTE: ORDER HERE IS EXTREMELY IMPORTANT
                         // The commitment MUST be calculated after encryption, otherwise
                         // the nonce is not set and the commitment turns out wrong.
@@ -281,6 +284,12 @@ pub fn process_circuit_outputs(
                         VariableType::Private(UserInputValueType::Boolean(b.value()?))
                     }
                     SimpleField(f) => VariableType::Private(UserInputValueType::Field(f.value()?)),
+                    SimpleStruct(members) => VariableType::Private(UserInputValueType::Struct(
+                        primitive_struct_members(members)?,
+                    )),
+                    SimpleString(s) => {
+                        VariableType::Private(UserInputValueType::String(s.value()?))
+                    }
                 }
             } else {
                 match program_variable {
@@ -303,6 +312,10 @@ pub fn process_circuit_outputs(
                         VariableType::Public(UserInputValueType::Boolean(b.value()?))
                     }
                     SimpleField(f) => VariableType::Public(UserInputValueType::Field(f.value()?)),
+                    SimpleStruct(members) => VariableType::Public(UserInputValueType::Struct(
+                        primitive_struct_members(members)?,
+                    )),
+                    SimpleString(s) => VariableType::Public(UserInputValueType::String(s.value()?)),
                 }
             }
         });
@@ -310,3 +323,52 @@ pub fn process_circuit_outputs(
     })?;
     Ok(circuit_outputs)
 }
+
+/// Converts a non-record gadget into its primitive value.
+fn primitive_value(value: CircuitIOType) -> Result<UserInputValueType> {
+    let primitive_value = match value {
+        SimpleUInt8(v) => UserInputValueType::U8(v.value()?),
+        SimpleInt8(v) => UserInputValueType::I8(v.value()?),
+        SimpleUInt16(v) => UserInputValueType::U16(v.value()?),
+        SimpleUInt32(v) => UserInputValueType::U32(v.value()?),
+        SimpleUInt64(v) => UserInputValueType::U64(v.value()?),
+        SimpleRecord(_) => bail!("Records cannot be converted into a primitive value"),
+        SimpleAddress(v) => UserInputValueType::Address(to_address(v.value()?)),
+        SimpleBoolean(b) => UserInputValueType::Boolean(b.value()?),
+        SimpleField(f) => UserInputValueType::Field(f.value()?),
+        SimpleStruct(members) => UserInputValueType::Struct(primitive_struct_members(members)?),
+        SimpleString(s) => UserInputValueType::String(s.value()?),
+    };
+    Ok(primitive_value)
+}
+
+/// Converts the entries of a record gadget into their primitive values. The
+/// visibility of each entry is the one declared by the record type.
+fn primitive_record_entries(
+    entries: VMRecordEntriesMap,
+    entry_types: &IndexMap<Identifier, EntryType<Testnet3>>,
+) -> Result<RecordEntriesMap> {
+    let mut primitive_entries = RecordEntriesMap::new();
+    for (k, v) in entries {
+        let entry_type = entry_types
+            .get(&Identifier::from_str(&k)?)
+            .ok_or_else(|| anyhow!("Record entry `{k}` is not declared in its record type"))?;
+        let primitive_value = match v {
+            SimpleRecord(_) => bail!("Nested records are not supported"),
+            v => primitive_value(v)?,
+        };
+        primitive_entries.insert(
+            k,
+            RecordEntry::new(primitive_value, Visibility::from(entry_type)),
+        );
+    }
+    Ok(primitive_entries)
+}
+/// Converts the members of a struct gadget into their primitive values.
+pub(crate) fn primitive_struct_members(members: VMStructMembersMap) -> Result<StructMembersMap> {
+    let mut primitive_members = StructMembersMap::new();
+    for (k, v) in members {
+        primitive_members.insert(k, primitive_value(v)?);
+    }
+    Ok(primitive_members)
+}
@@ -2,31 +2,48 @@ use std::str::FromStr;
 
 use anyhow::{anyhow, Result};
 use rand::SeedableRng;
-use simpleworks::{
-    gadgets::ConstraintF,
-    marlin::{generate_rand, MarlinProof},
-};
+use simpleworks::{gadgets::ConstraintF, marlin::MarlinProof};
 pub use snarkvm::prelude::Itertools;
 use snarkvm::prelude::{Scalar, Testnet3, Uniform};
 
+mod account;
+pub use account::Account;
+
 mod execute;
-pub use execute::{credits_execution, execution, process_circuit_inputs, process_circuit_outputs};
+pub(crate) use execute::primitive_struct_members;
+pub use execute::{
+    credits_execution, execution, execution_with_imports, process_circuit_inputs,
+    process_circuit_outputs,
+};
 
 mod deploy;
-pub use deploy::{generate_deployment, Deployment, VerifyingKeyMap};
+pub use deploy::{
+    check_deployment, check_deployment_with_imports, generate_deployment,
+    generate_deployment_with_imports, Deployment, VerifyingKeyMap,
+};
+
+mod encoding;
+pub use encoding::{
+    EncodedProof, EncodedVerifyingKey, PROOF_PREFIX, RECORD_PREFIX, VERIFYING_KEY_PREFIX,
+};
 
 mod types;
-pub use types::{Address as AddressBytes, RecordEntriesMap, UserInputValueType};
+pub use types::{
+    Address as AddressBytes, LiteralError, RecordEntriesMap, RecordEntry, StructMembersMap,
+    UserInputValueType, Visibility,
+};
 
 mod record;
 // Rename to Record when we get rid of snarkVM's.
-pub use record::{EncryptedRecord, Record};
+pub use record::{EncryptedRecord, EncryptionScheme, Record};
 
 mod transition;
 pub use transition::Transition;
 
+mod verify;
+pub use verify::{verify_execution, verify_execution_with_imports};
+
 use crate::{
-    build_function,
     helpers::{self, default_user_inputs},
     FunctionKeys,
 };
@@ -92,17 +109,18 @@ pub fn mint_credits(
     Ok((non_encrypted_record.commitment()?, encrypted_record))
 }
 
+/// Builds the keys of a credits function from the stored universal
+/// parameters, trimmed to the size of the function like any other build.
 pub fn get_credits_key(program: &Program, function_name: &Identifier) -> Result<FunctionKeys> {
-    let universal_srs =
-        simpleworks::marlin::generate_universal_srs(100000, 25000, 300000, &mut generate_rand())?;
     let constraint_system = ark_relations::r1cs::ConstraintSystem::<ConstraintF>::new_ref();
     let function = program.get_function(function_name)?;
-    build_function(
+    crate::synthesize_function(
         program,
+        &[],
         &function,
-        &default_user_inputs(program, function_name)?,
-        constraint_system.clone(),
-        &universal_srs,
-        &mut helpers::function_variables(&function, constraint_system)?,
-    )
+        &default_user_inputs(program, &[], function_name)?,
+        &constraint_system,
+        &mut helpers::function_variables(&function, constraint_system.clone())?,
+    )?;
+    crate::generate_function_keys_with_stored_universal_srs(constraint_system)
 }
@@ -1,8 +1,12 @@
-use super::{Address, AddressBytes, PrivateKey, RecordEntriesMap, ViewKey};
+use super::{
+    encoding::{decode_bech32m, encode_bech32m},
+    Address, AddressBytes, PrivateKey, RecordEntriesMap, RecordEntry, UserInputValueType, ViewKey,
+    Visibility, RECORD_PREFIX,
+};
 use crate::helpers::{self};
 use aes::cipher::KeyInit;
 use aes_gcm::{AeadInPlace, Aes256Gcm};
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use digest::generic_array::GenericArray;
 use serde::{
     de,
@@ -10,7 +14,9 @@ use serde::{
     Deserialize, Serialize,
 };
 use sha3::{Digest, Sha3_256};
-use snarkvm::prelude::{Field, FromBytes, Group, Network, Scalar, Testnet3, ToBytes};
+use snarkvm::prelude::{
+    Ciphertext, Entry, Field, FromBytes, Group, Network, Plaintext, Scalar, Testnet3, ToBytes,
+};
 use std::{fmt::Display, str::FromStr};
 
 /// AES IV/nonce length
@@ -22,10 +28,41 @@ pub const AES_IV_PLUS_TAG_LENGTH: usize = AES_IV_LENGTH + AES_TAG_LENGTH;
 /// Empty bytes array
 pub const EMPTY_BYTES: [u8; 0] = [];
 
+type SnarkVMRecord = snarkvm::prelude::Record<Testnet3, Plaintext<Testnet3>>;
+type SnarkVMEncryptedRecord = snarkvm::prelude::Record<Testnet3, Ciphertext<Testnet3>>;
+
+/// How the private entries of a record are encrypted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum EncryptionScheme {
+    /// The record as json, encrypted with AES-GCM under a key derived with
+    /// SHA3 from the record view key.
+    #[default]
+    AesGcm,
+    /// The entries as field elements, encrypted with snarkVM's Poseidon based
+    /// symmetric scheme, so Aleo wallets can decrypt the record.
+    Poseidon,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct EncryptedRecord {
+    /// With AES-GCM, `record` followed by the hex of the nonce and the
+    /// encrypted record without its public and constant entries. With
+    /// Poseidon, the snarkVM ciphertext as `record1...`.
     pub ciphertext: String,
     pub nonce: Group<Testnet3>,
+    /// The public and constant entries, which are not encrypted.
+    #[serde(default)]
+    pub public_entries: RecordEntriesMap,
+    #[serde(default)]
+    pub scheme: EncryptionScheme,
+}
+
+// What gets encrypted: the record without its public and constant entries,
+// and the names of all of its entries so they can be put back in order.
+#[derive(Deserialize, Serialize)]
+struct PrivateRecord {
+    record: Record,
+    entry_names: Vec<String>,
 }
 
 /*
@@ -49,6 +86,21 @@ pub struct EncryptedRecord {
 
 impl EncryptedRecord {
     pub fn decrypt(&self, view_key: &ViewKey) -> Result<Record> {
+        if self.scheme == EncryptionScheme::Poseidon {
+            let plaintext_record =
+                SnarkVMEncryptedRecord::from_str(&self.ciphertext)?.decrypt(view_key)?;
+            let record = Record::from_plaintext(&plaintext_record.to_string())?;
+            let public_entries = record
+                .data
+                .iter()
+                .filter(|(_name, entry)| !entry.is_private());
+            ensure!(
+                public_entries.eq(&self.public_entries),
+                "The public entries do not match the ones of the encrypted record"
+            );
+            return Ok(record);
+        }
+
         let record_view_key = (**view_key * self.nonce).to_x_coordinate();
 
         let ciphertext = hex::decode(
@@ -61,7 +113,39 @@ impl EncryptedRecord {
             .get(32..)
             .ok_or_else(|| anyhow!("Out of bounds when slicing ciphertext"))?;
 
-        decrypt_from_record_view_key(&record_view_key, ciphertext)
+        let private_record = decrypt_from_record_view_key(&record_view_key, ciphertext)?;
+        self.with_public_entries(private_record)
+    }
+
+    // Puts the public entries back into the decrypted record. Records
+    // encrypted before entries had a visibility hold all of their entries.
+    fn with_public_entries(&self, private_record: PrivateRecord) -> Result<Record> {
+        let PrivateRecord {
+            record: mut private_record,
+            entry_names,
+        } = private_record;
+        let mut data = RecordEntriesMap::new();
+        let mut public_entries_count = 0_usize;
+        for name in entry_names {
+            let entry = match (
+                private_record.data.shift_remove(&name),
+                self.public_entries.get(&name),
+            ) {
+                (Some(entry), None) if entry.is_private() => entry,
+                (None, Some(entry)) if !entry.is_private() => {
+                    public_entries_count += 1;
+                    entry.clone()
+                }
+                _ => bail!("Entry `{name}` of the encrypted record is invalid or missing"),
+            };
+            data.insert(name, entry);
+        }
+        ensure!(
+            private_record.data.is_empty() && public_entries_count == self.public_entries.len(),
+            "The encrypted record has entries that are not in the record"
+        );
+        private_record.data = data;
+        Ok(private_record)
     }
 
     pub fn is_owner(&self, address: &Address, view_key: &ViewKey) -> bool {
@@ -73,31 +157,41 @@ impl EncryptedRecord {
         false
     }
 
+    /// Decrypts a record given as either `record1...` or the legacy
+    /// `record` followed by hex.
     pub fn decrypt_from_ciphertext(view_key: &ViewKey, ciphertext: &str) -> Result<Record> {
-        // The first six bytes are the "record" string
-        let decoded = hex::decode(
-            ciphertext
-                .get(6..)
-                .ok_or_else(|| anyhow!("Out of bounds when slicing ciphertext"))?,
-        )?;
-        let nonce_bytes = decoded
-            .get(..32)
-            .ok_or_else(|| anyhow!("Out of bounds when getting nonce from ciphertext"))?;
-        let ciphertext = decoded
-            .get(32..)
-            .ok_or_else(|| anyhow!("Out of bounds when getting ciphertext"))?;
-
-        let nonce = Group::<Testnet3>::from_bytes_le(nonce_bytes)?;
-        let record_view_key = (**view_key * nonce).to_x_coordinate();
+        EncryptedRecord::from_str(ciphertext)?.decrypt(view_key)
+    }
 
-        decrypt_from_record_view_key(&record_view_key, ciphertext)
+    fn from_snarkvm(record: &SnarkVMEncryptedRecord) -> Result<Self> {
+        let mut public_entries = RecordEntriesMap::new();
+        for (identifier, entry) in record.data() {
+            let (plaintext, visibility) = match entry {
+                Entry::Constant(plaintext) => (plaintext, Visibility::Constant),
+                Entry::Public(plaintext) => (plaintext, Visibility::Public),
+                Entry::Private(_) => continue,
+            };
+            public_entries.insert(
+                identifier.to_string(),
+                RecordEntry::new(
+                    UserInputValueType::try_from(plaintext.to_string())?,
+                    visibility,
+                ),
+            );
+        }
+        Ok(Self {
+            ciphertext: record.to_string(),
+            nonce: *record.nonce(),
+            public_entries,
+            scheme: EncryptionScheme::Poseidon,
+        })
     }
 }
 
 fn decrypt_from_record_view_key(
     record_view_key: &Field<Testnet3>,
     ciphertext: &[u8],
-) -> Result<Record> {
+) -> Result<PrivateRecord> {
     let mut hasher = Sha3_256::new();
     hasher.update(record_view_key.to_bytes_le()?);
     let key = hasher.finalize().to_vec();
@@ -126,19 +220,40 @@ fn decrypt_from_record_view_key(
     aead.decrypt_in_place_detached(iv, &EMPTY_BYTES, &mut plaintext, tag)
         .map_err(|e| anyhow!("{}", e))?;
 
-    let record = serde_json::from_slice(&plaintext)?;
-    Ok(record)
+    match serde_json::from_slice::<PrivateRecord>(&plaintext) {
+        Ok(private_record) => Ok(private_record),
+        Err(_) => {
+            let record: Record = serde_json::from_slice(&plaintext)?;
+            let entry_names = record.data.keys().cloned().collect();
+            Ok(PrivateRecord {
+                record,
+                entry_names,
+            })
+        }
+    }
 }
 
 impl FromStr for EncryptedRecord {
     type Err = anyhow::Error;
 
+    /// Parses a `record1...` bech32m string, either a snarkVM ciphertext or
+    /// one encrypted with AES-GCM. The legacy `record` followed by hex, which
+    /// has no public entries, is still accepted, anything else is reported as
+    /// a bech32m error so mistyped or truncated records are caught.
     fn from_str(s: &str) -> Result<Self> {
-        // The first six bytes are the "record" string.
-        let record_as_bytes = hex::decode(
-            s.get(6..)
-                .ok_or_else(|| anyhow!("Out of bounds when slicing the record"))?,
-        )?;
+        let s = s.trim();
+        if let Ok(record) = SnarkVMEncryptedRecord::from_str(s) {
+            return Self::from_snarkvm(&record);
+        }
+        let legacy_hex = s
+            .strip_prefix(RECORD_PREFIX)
+            .filter(|hex| hex.len() % 2 == 0 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+        let (record_as_bytes, public_entries) = match (decode_bech32m(RECORD_PREFIX, s), legacy_hex)
+        {
+            (Ok(encoded_bytes), _) => split_public_entries(&encoded_bytes)?,
+            (Err(_), Some(legacy_hex)) => (hex::decode(legacy_hex)?, RecordEntriesMap::new()),
+            (Err(e), None) => return Err(e),
+        };
         let nonce_bytes = record_as_bytes
             .get(..32)
             .ok_or_else(|| anyhow!("Out of bounds when accessing the record nonce"))?;
@@ -147,13 +262,65 @@ impl FromStr for EncryptedRecord {
         let mut ciphertext = "record".to_owned();
         ciphertext.push_str(&hex::encode(record_as_bytes));
 
-        Ok(Self { ciphertext, nonce })
+        Ok(Self {
+            ciphertext,
+            nonce,
+            public_entries,
+            scheme: EncryptionScheme::AesGcm,
+        })
     }
 }
 
+// The bech32m encoding of a record starts with the nonce, followed by the
+// length of the public entries as a little endian `u32`, the public entries
+// as json and the ciphertext.
+fn split_public_entries(encoded_bytes: &[u8]) -> Result<(Vec<u8>, RecordEntriesMap)> {
+    let out_of_bounds = || anyhow!("Out of bounds when accessing the record public entries");
+    let nonce_bytes = encoded_bytes.get(..32).ok_or_else(out_of_bounds)?;
+    let length_bytes = encoded_bytes.get(32..36).ok_or_else(out_of_bounds)?;
+    let length = usize::try_from(u32::from_le_bytes(length_bytes.try_into()?))?;
+    let public_entries_end = length.checked_add(36).ok_or_else(out_of_bounds)?;
+    let public_entries = serde_json::from_slice(
+        encoded_bytes
+            .get(36..public_entries_end)
+            .ok_or_else(out_of_bounds)?,
+    )?;
+
+    let mut record_as_bytes = nonce_bytes.to_vec();
+    record_as_bytes.extend_from_slice(
+        encoded_bytes
+            .get(public_entries_end..)
+            .ok_or_else(out_of_bounds)?,
+    );
+    Ok((record_as_bytes, public_entries))
+}
+
 impl Display for EncryptedRecord {
+    /// Displays the record as `record1...`, with the nonce followed by the
+    /// public entries and the ciphertext encoded as bech32m. Records encrypted
+    /// with Poseidon are shown as in snarkVM.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.ciphertext)
+        if self.scheme == EncryptionScheme::Poseidon {
+            return write!(f, "{}", self.ciphertext);
+        }
+        // The first six bytes are the "record" string.
+        let record_as_bytes = self
+            .ciphertext
+            .get(6..)
+            .and_then(|ciphertext| hex::decode(ciphertext).ok())
+            .ok_or(std::fmt::Error)?;
+        let public_entries =
+            serde_json::to_vec(&self.public_entries).map_err(std::fmt::Error::custom)?;
+        let public_entries_length =
+            u32::try_from(public_entries.len()).map_err(std::fmt::Error::custom)?;
+
+        let mut encoded_bytes = record_as_bytes.get(..32).ok_or(std::fmt::Error)?.to_vec();
+        encoded_bytes.extend_from_slice(&public_entries_length.to_le_bytes());
+        encoded_bytes.extend(public_entries);
+        encoded_bytes.extend_from_slice(record_as_bytes.get(32..).ok_or(std::fmt::Error)?);
+        let encoded =
+            encode_bech32m(RECORD_PREFIX, &encoded_bytes).map_err(|_e| std::fmt::Error)?;
+        write!(f, "{encoded}")
     }
 }
 
@@ -262,6 +429,29 @@ impl Record {
     /// Encrypting takes a mutable reference because the act of encrypting is what decides the nonce
     /// of the record which was previously None, so we mutate it.
     pub fn encrypt(&mut self, randomizer: Scalar<Testnet3>) -> Result<EncryptedRecord> {
+        self.encrypt_with_scheme(randomizer, EncryptionScheme::AesGcm)
+    }
+
+    /// Same as `encrypt` but with the given encryption scheme.
+    pub fn encrypt_with_scheme(
+        &mut self,
+        randomizer: Scalar<Testnet3>,
+        scheme: EncryptionScheme,
+    ) -> Result<EncryptedRecord> {
+        match scheme {
+            EncryptionScheme::AesGcm => self.encrypt_aes_gcm(randomizer),
+            EncryptionScheme::Poseidon => self.encrypt_poseidon(randomizer),
+        }
+    }
+
+    // snarkVM takes the record with Aleo's syntax, which needs the nonce.
+    fn encrypt_poseidon(&mut self, randomizer: Scalar<Testnet3>) -> Result<EncryptedRecord> {
+        self.nonce = Some(Testnet3::g_scalar_multiply(&randomizer));
+        let record = SnarkVMRecord::from_str(&self.to_plaintext()?)?;
+        EncryptedRecord::from_snarkvm(&record.encrypt(randomizer)?)
+    }
+
+    fn encrypt_aes_gcm(&mut self, randomizer: Scalar<Testnet3>) -> Result<EncryptedRecord> {
         let address_string = String::from_utf8(self.owner.to_vec())?;
         let address = Address::from_str(&address_string)?;
         let record_nonce = Testnet3::g_scalar_multiply(&randomizer);
@@ -284,7 +474,20 @@ impl Record {
 
         let nonce = GenericArray::from_slice(&iv);
 
-        let message = serde_json::to_vec(self)?;
+        let (private_data, public_entries): (RecordEntriesMap, RecordEntriesMap) = self
+            .data
+            .iter()
+            .map(|(name, entry)| (name.clone(), entry.clone()))
+            .partition(|(_name, entry)| entry.is_private());
+        let private_record = PrivateRecord {
+            record: Record {
+                data: private_data,
+                ..self.clone()
+            },
+            entry_names: self.data.keys().cloned().collect(),
+        };
+
+        let message = serde_json::to_vec(&private_record)?;
         let message_length = message.len();
         let mut out = Vec::with_capacity(message_length);
         out.extend(message);
@@ -312,6 +515,8 @@ impl Record {
         Ok(EncryptedRecord {
             ciphertext,
             nonce: record_nonce,
+            public_entries,
+            scheme: EncryptionScheme::AesGcm,
         })
     }
 
@@ -350,13 +555,17 @@ impl Serialize for Record {
 mod tests {
     use crate::{
         helpers,
-        jaleo::{Address, AddressBytes, PrivateKey, RecordEntriesMap, ViewKey},
+        jaleo::{
+            Address, AddressBytes, PrivateKey, RecordEntriesMap, RecordEntry, UserInputValueType,
+            ViewKey,
+        },
     };
 
-    use super::{EncryptedRecord, Record};
+    use super::{EncryptedRecord, EncryptionScheme, Record, SnarkVMRecord};
     use ark_ff::UniformRand;
     use indexmap::IndexMap;
-    use snarkvm::prelude::Scalar;
+    use snarkvm::prelude::{Network, Scalar, Testnet3};
+    use std::str::FromStr;
 
     fn address(n: u64) -> (String, AddressBytes) {
         let mut address_bytes = [0_u8; 63];
@@ -500,4 +709,132 @@ mod tests {
 
         assert_eq!(decrypted, record);
     }
+
+    #[test]
+    fn test_record_bech32m_encoding() {
+        let rng = &mut rand::thread_rng();
+        let private_key = PrivateKey::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let address = Address::try_from(&view_key).unwrap();
+        let address = helpers::to_address(address.to_string());
+
+        let mut record = Record::new(address, 1, IndexMap::new(), None);
+        let encrypted = record.encrypt(Scalar::rand(rng)).unwrap();
+        let encoded = encrypted.to_string();
+
+        assert!(encoded.starts_with("record1"));
+        assert_eq!(encoded.parse::<EncryptedRecord>().unwrap(), encrypted);
+        assert_eq!(
+            EncryptedRecord::decrypt_from_ciphertext(&view_key, &encoded).unwrap(),
+            record
+        );
+
+        let mut truncated = encoded;
+        truncated.pop();
+        assert!(truncated.parse::<EncryptedRecord>().is_err());
+    }
+
+    #[test]
+    fn test_public_entries_are_not_encrypted() {
+        let rng = &mut rand::thread_rng();
+        let private_key = PrivateKey::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let address = Address::try_from(&view_key).unwrap();
+        let address = helpers::to_address(address.to_string());
+
+        let mut data = RecordEntriesMap::new();
+        data.insert("secret".to_owned(), UserInputValueType::U64(1).into());
+        data.insert(
+            "amount".to_owned(),
+            RecordEntry::public(UserInputValueType::U64(2)),
+        );
+        data.insert("flag".to_owned(), UserInputValueType::Boolean(true).into());
+        let mut record = Record::new(address, 1, data, None);
+        let encrypted = record.encrypt(Scalar::rand(rng)).unwrap();
+
+        assert_eq!(encrypted.public_entries.len(), 1);
+        assert_eq!(
+            encrypted.public_entries.get("amount"),
+            Some(&RecordEntry::public(UserInputValueType::U64(2)))
+        );
+        let decrypted = encrypted.decrypt(&view_key).unwrap();
+        assert_eq!(decrypted, record);
+        assert_eq!(
+            decrypted.data.keys().collect::<Vec<_>>(),
+            ["secret", "amount", "flag"]
+        );
+
+        let encoded = encrypted.to_string();
+        assert_eq!(encoded.parse::<EncryptedRecord>().unwrap(), encrypted);
+        assert_eq!(
+            EncryptedRecord::decrypt_from_ciphertext(&view_key, &encoded).unwrap(),
+            record
+        );
+
+        // The public entries cannot be dropped or made private.
+        let mut tampered = encrypted.clone();
+        tampered.public_entries.clear();
+        assert!(tampered.decrypt(&view_key).is_err());
+        let mut tampered = encrypted;
+        tampered
+            .public_entries
+            .insert("amount".to_owned(), UserInputValueType::U64(2).into());
+        assert!(tampered.decrypt(&view_key).is_err());
+    }
+
+    #[test]
+    fn test_poseidon_record_encryption() {
+        let rng = &mut rand::thread_rng();
+        let private_key = PrivateKey::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let address = Address::try_from(&view_key).unwrap();
+        let address_bytes = helpers::to_address(address.to_string());
+
+        let mut data = RecordEntriesMap::new();
+        data.insert("secret".to_owned(), UserInputValueType::U64(1).into());
+        data.insert(
+            "amount".to_owned(),
+            RecordEntry::public(UserInputValueType::U64(2)),
+        );
+        let mut record = Record::new(address_bytes, 1, data, None);
+        let encrypted = record
+            .encrypt_with_scheme(Scalar::rand(rng), EncryptionScheme::Poseidon)
+            .unwrap();
+
+        assert_eq!(encrypted.scheme, EncryptionScheme::Poseidon);
+        assert_eq!(
+            encrypted.public_entries.get("amount"),
+            Some(&RecordEntry::public(UserInputValueType::U64(2)))
+        );
+        assert_eq!(encrypted.decrypt(&view_key).unwrap(), record);
+        assert!(encrypted.is_owner(&address, &view_key));
+
+        let encoded = encrypted.to_string();
+        assert!(encoded.starts_with("record1"));
+        assert_eq!(encoded.parse::<EncryptedRecord>().unwrap(), encrypted);
+        assert_eq!(
+            EncryptedRecord::decrypt_from_ciphertext(&view_key, &encoded).unwrap(),
+            record
+        );
+    }
+
+    #[test]
+    fn test_decrypt_snarkvm_record() {
+        let rng = &mut rand::thread_rng();
+        let private_key = PrivateKey::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let address = Address::try_from(&view_key).unwrap();
+        let randomizer = Scalar::rand(rng);
+        let nonce = Testnet3::g_scalar_multiply(&randomizer);
+        let plaintext = format!(
+            "{{ owner: {address}.private, gates: 5u64.private, amount: 3u64.public, _nonce: {nonce}.public }}"
+        );
+
+        let snarkvm_record = SnarkVMRecord::from_str(&plaintext).unwrap();
+        let ciphertext = snarkvm_record.encrypt(randomizer).unwrap().to_string();
+        let record = EncryptedRecord::decrypt_from_ciphertext(&view_key, &ciphertext).unwrap();
+
+        assert_eq!(record, Record::from_plaintext(&plaintext).unwrap());
+        assert!(!record.data["amount"].is_private());
+    }
 }
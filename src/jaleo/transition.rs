@@ -1,6 +1,16 @@
-use super::{EncryptedRecord, Identifier, ProgramID};
-use crate::variable_type::VariableType;
+use super::{EncryptedRecord, Identifier, ProgramID, Record, UserInputValueType, ViewKey};
+use crate::{
+    binary::{self, Kind},
+    variable_type::VariableType,
+};
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{Read, Write},
+    path::Path,
+    str::FromStr,
+};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Transition {
@@ -27,6 +37,88 @@ pub struct Transition {
 }
 
 impl Transition {
+    /// Reads a transition in either the JSON or the binary format.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        if binary::is_binary_file(path)? {
+            return Self::read_binary(&mut binary::open_file(path)?);
+        }
+        let serialized_transition = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Error reading transition {}: {e}", path.display()))?;
+        Ok(serde_json::from_str(&serialized_transition)?)
+    }
+
+    /// Reads the transitions of a file, which holds either a single
+    /// transition in the JSON or the binary format, or JSON lines with one
+    /// transition per line as `execute --transitions` prints them.
+    pub fn read_all_from_file(path: &Path) -> Result<Vec<Self>> {
+        if binary::is_binary_file(path)? {
+            return Ok(vec![Self::read_binary(&mut binary::open_file(path)?)?]);
+        }
+        let serialized_transitions = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Error reading transitions {}: {e}", path.display()))?;
+        if let Ok(transition) = serde_json::from_str(&serialized_transitions) {
+            return Ok(vec![transition]);
+        }
+        serialized_transitions
+            .lines()
+            .enumerate()
+            .filter(|(_line_number, line)| !line.trim().is_empty())
+            .map(|(line_number, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    anyhow!("Error parsing transition at line {}: {e}", line_number + 1)
+                })
+            })
+            .collect()
+    }
+
+    /// Writes the transitions as JSON lines, one compact transition per line.
+    pub fn to_json_lines(transitions: &[Self]) -> Result<String> {
+        Ok(transitions
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?
+            .join("\n"))
+    }
+
+    pub fn write_to_binary_file(&self, path: &Path) -> Result<()> {
+        let mut writer = binary::create_file(path)?;
+        self.write_binary(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// The proof is written as its raw bytes instead of hex, and the inputs
+    /// and outputs with bincode.
+    pub fn write_binary<W: Write>(&self, writer: &mut W) -> Result<()> {
+        binary::write_header(writer, Kind::Transition)?;
+        binary::write_string(writer, &self.program_id.to_string())?;
+        binary::write_string(writer, &self.function_name.to_string())?;
+        bincode::serialize_into(&mut *writer, &self.inputs)?;
+        bincode::serialize_into(&mut *writer, &self.outputs)?;
+        binary::write_bytes(writer, &hex::decode(&self.proof)?)?;
+        writer.write_all(&self.fee.to_le_bytes())?;
+        Ok(())
+    }
+
+    pub fn read_binary<R: Read>(reader: &mut R) -> Result<Self> {
+        binary::read_header(reader, Kind::Transition)?;
+        let program_id = ProgramID::from_str(&binary::read_string(reader)?)?;
+        let function_name = Identifier::from_str(&binary::read_string(reader)?)?;
+        let inputs = bincode::deserialize_from(&mut *reader)?;
+        let outputs = bincode::deserialize_from(&mut *reader)?;
+        let proof = hex::encode(binary::read_bytes(reader)?);
+        let mut fee = [0_u8; 8];
+        reader.read_exact(&mut fee)?;
+        Ok(Self {
+            program_id,
+            function_name,
+            inputs,
+            outputs,
+            proof,
+            fee: i64::from_le_bytes(fee),
+        })
+    }
+
     pub fn output_records(&self) -> Vec<(String, EncryptedRecord)> {
         self.outputs
             .clone()
@@ -41,6 +133,19 @@ impl Transition {
             .collect()
     }
 
+    /// Returns the output records owned by the given view key, decrypted and
+    /// along with their commitments.
+    pub fn owned_records(&self, view_key: &ViewKey) -> Vec<(String, Record)> {
+        let owner = crate::helpers::to_address(view_key.to_address().to_string());
+        self.output_records()
+            .into_iter()
+            .filter_map(|(commitment, encrypted_record)| {
+                let record = encrypted_record.decrypt(view_key).ok()?;
+                (record.owner == owner).then_some((commitment, record))
+            })
+            .collect()
+    }
+
     pub fn origins(&self) -> Vec<String> {
         self.input_records()
             .iter()
@@ -62,6 +167,21 @@ impl Transition {
             .collect()
     }
 
+    /// Returns the values of the constant inputs, which are needed to build
+    /// the verifying key the transition proof was generated for.
+    pub fn constant_inputs(&self) -> Vec<UserInputValueType> {
+        self.inputs
+            .iter()
+            .filter_map(|input| {
+                if let VariableType::Constant(value) = input {
+                    Some(value.clone())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     pub fn serial_numbers(&self) -> Vec<String> {
         self.inputs
             .iter()
@@ -0,0 +1,424 @@
+//! Parser of Aleo literals, following the grammar
+//!
+//! ```text
+//! literal    = value [ "." visibility ]
+//! value      = string | boolean | address | number type
+//! string     = '"' { character } '"'
+//! boolean    = "true" | "false"
+//! address    = "aleo1" 58 * ( lowercase letter | digit )
+//! number     = [ "-" ] digit { digit | "_" }
+//! type       = "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "field" | "group" | "scalar"
+//! visibility = "constant" | "public" | "private"
+//! ```
+//!
+//! Every literal parsed is displayed back as the same literal (up to
+//! underscores, leading zeros and visibility).
+
+use super::{UserInputValueType, Visibility};
+use crate::MAX_STRING_BYTES;
+use ark_ff::{BigInteger, PrimeField};
+use simpleworks::gadgets::ConstraintF;
+use snarkvm::prelude::{Group, Scalar, Testnet3};
+use std::{fmt, str::FromStr};
+
+const ADDRESS_PREFIX: &str = "aleo1";
+const ADDRESS_LENGTH: usize = 63;
+
+/// Errors found while parsing a literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralError {
+    /// There is no literal at all.
+    Empty,
+    /// The literal has no type or one Aleo does not have, e.g. `5` or `5u7`.
+    UnknownType(String),
+    /// The type exists in Aleo but is not supported yet, e.g. `5i16`.
+    UnsupportedType(String),
+    /// The number does not fit its type or is malformed, e.g. `256u8`.
+    InvalidNumber { literal: String, reason: String },
+    /// The address has the wrong length or characters.
+    InvalidAddress(String),
+    /// The group element is not on the curve.
+    InvalidGroup { literal: String, reason: String },
+    /// The scalar is malformed or does not fit the scalar field.
+    InvalidScalar { literal: String, reason: String },
+    /// The string has no closing quote or has quotes inside.
+    InvalidString(String),
+    /// The string has more than `MAX_STRING_BYTES` bytes.
+    StringTooLong(usize),
+    /// The suffix after the dot is not a visibility, e.g. `5u8.secret`.
+    InvalidVisibility(String),
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LiteralError::Empty => write!(f, "Expected a literal but got nothing"),
+            LiteralError::UnknownType(literal) => write!(
+                f,
+                "Unknown type of literal `{literal}`, expected a string, boolean, address, u8, u16, u32, u64, u128, i8, field, group or scalar"
+            ),
+            LiteralError::UnsupportedType(literal) => {
+                write!(f, "The type of literal `{literal}` is not supported yet")
+            }
+            LiteralError::InvalidNumber { literal, reason } => {
+                write!(f, "Invalid number `{literal}`: {reason}")
+            }
+            LiteralError::InvalidAddress(literal) => write!(
+                f,
+                "Invalid address `{literal}`, expected {ADDRESS_LENGTH} lowercase letters and digits starting with {ADDRESS_PREFIX}"
+            ),
+            LiteralError::InvalidGroup { literal, reason } => {
+                write!(f, "Invalid group `{literal}`: {reason}")
+            }
+            LiteralError::InvalidScalar { literal, reason } => {
+                write!(f, "Invalid scalar `{literal}`: {reason}")
+            }
+            LiteralError::InvalidString(literal) => {
+                write!(f, "Invalid string {literal}, expected it to be enclosed in quotes")
+            }
+            LiteralError::StringTooLong(length) => write!(
+                f,
+                "Strings can have at most {MAX_STRING_BYTES} bytes, got {length}"
+            ),
+            LiteralError::InvalidVisibility(visibility) => write!(
+                f,
+                "Invalid visibility `{visibility}`, expected constant, public or private"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+impl UserInputValueType {
+    /// Parses an Aleo literal, e.g. `5u8`, `-1i8.private`, `"hello"` or
+    /// `aleo1....public`, returning it along with its visibility if it has one.
+    pub fn parse_literal(literal: &str) -> Result<(Self, Option<Visibility>), LiteralError> {
+        let literal = literal.trim();
+        if literal.is_empty() {
+            return Err(LiteralError::Empty);
+        }
+        if literal.starts_with('"') {
+            return parse_string(literal);
+        }
+
+        let (value, visibility) = match literal.split_once('.') {
+            Some((value, visibility)) => (
+                value,
+                Some(
+                    Visibility::from_str(visibility)
+                        .map_err(|_e| LiteralError::InvalidVisibility(visibility.to_owned()))?,
+                ),
+            ),
+            None => (literal, None),
+        };
+        Ok((parse_value(value)?, visibility))
+    }
+}
+
+fn parse_string(literal: &str) -> Result<(UserInputValueType, Option<Visibility>), LiteralError> {
+    let (string, rest) = literal
+        .get(1..)
+        .and_then(|l| l.split_once('"'))
+        .ok_or_else(|| LiteralError::InvalidString(literal.to_owned()))?;
+    let visibility = match rest {
+        "" => None,
+        _ => {
+            let visibility = rest
+                .strip_prefix('.')
+                .ok_or_else(|| LiteralError::InvalidString(literal.to_owned()))?;
+            Some(
+                Visibility::from_str(visibility)
+                    .map_err(|_e| LiteralError::InvalidVisibility(visibility.to_owned()))?,
+            )
+        }
+    };
+    if string.len() > MAX_STRING_BYTES {
+        return Err(LiteralError::StringTooLong(string.len()));
+    }
+    Ok((UserInputValueType::String(string.to_owned()), visibility))
+}
+
+fn parse_value(value: &str) -> Result<UserInputValueType, LiteralError> {
+    match value {
+        "true" => return Ok(UserInputValueType::Boolean(true)),
+        "false" => return Ok(UserInputValueType::Boolean(false)),
+        _ => {}
+    }
+    if value.starts_with(ADDRESS_PREFIX) {
+        return parse_address(value);
+    }
+
+    let type_start = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '_' || c == '-'))
+        .ok_or_else(|| LiteralError::UnknownType(value.to_owned()))?;
+    let (number, type_name) = value.split_at(type_start);
+    if number.is_empty() {
+        return Err(LiteralError::UnknownType(value.to_owned()));
+    }
+    let digits = number.replace('_', "");
+
+    let invalid_number = |reason: String| LiteralError::InvalidNumber {
+        literal: value.to_owned(),
+        reason,
+    };
+    match type_name {
+        "u8" => Ok(UserInputValueType::U8(
+            digits.parse().map_err(|e| invalid_number(format!("{e}")))?,
+        )),
+        "u16" => Ok(UserInputValueType::U16(
+            digits.parse().map_err(|e| invalid_number(format!("{e}")))?,
+        )),
+        "u32" => Ok(UserInputValueType::U32(
+            digits.parse().map_err(|e| invalid_number(format!("{e}")))?,
+        )),
+        "u64" => Ok(UserInputValueType::U64(
+            digits.parse().map_err(|e| invalid_number(format!("{e}")))?,
+        )),
+        "u128" => Ok(UserInputValueType::U128(
+            digits.parse().map_err(|e| invalid_number(format!("{e}")))?,
+        )),
+        "i8" => Ok(UserInputValueType::I8(
+            digits.parse().map_err(|e| invalid_number(format!("{e}")))?,
+        )),
+        "field" => Ok(UserInputValueType::Field(
+            parse_field(&digits).map_err(invalid_number)?,
+        )),
+        "group" => Group::<Testnet3>::from_str(&format!("{digits}group"))
+            .map(UserInputValueType::Group)
+            .map_err(|e| LiteralError::InvalidGroup {
+                literal: value.to_owned(),
+                reason: e.to_string(),
+            }),
+        "scalar" => Scalar::<Testnet3>::from_str(&format!("{digits}scalar"))
+            .map(UserInputValueType::Scalar)
+            .map_err(|e| LiteralError::InvalidScalar {
+                literal: value.to_owned(),
+                reason: e.to_string(),
+            }),
+        "i16" | "i32" | "i64" | "i128" => Err(LiteralError::UnsupportedType(value.to_owned())),
+        _ => Err(LiteralError::UnknownType(value.to_owned())),
+    }
+}
+
+fn parse_address(value: &str) -> Result<UserInputValueType, LiteralError> {
+    let is_valid = value.len() == ADDRESS_LENGTH
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+    if !is_valid {
+        return Err(LiteralError::InvalidAddress(value.to_owned()));
+    }
+    let mut address = [0_u8; ADDRESS_LENGTH];
+    address.copy_from_slice(value.as_bytes());
+    Ok(UserInputValueType::Address(address))
+}
+
+// Fields are parsed from their decimal representation, which has to be
+// smaller than the modulus so it is displayed back the same.
+fn parse_field(digits: &str) -> Result<ConstraintF, String> {
+    if digits.starts_with('-') {
+        return Err("fields cannot be negative".to_owned());
+    }
+    let digits = match digits.trim_start_matches('0') {
+        "" => "0",
+        digits => digits,
+    };
+    let field = ConstraintF::from_str(digits).map_err(|_e| "not a number".to_owned())?;
+    if field_to_decimal(&field) != digits {
+        return Err("it does not fit in the field".to_owned());
+    }
+    Ok(field)
+}
+
+/// Returns the decimal representation of the field element.
+pub(crate) fn field_to_decimal(field: &ConstraintF) -> String {
+    let mut bytes = field.into_repr().to_bytes_be();
+    let mut digits = Vec::new();
+    while bytes.iter().any(|byte| *byte != 0) {
+        // Long division of the big endian bytes by 10.
+        let mut remainder = 0_u16;
+        for byte in bytes.iter_mut() {
+            let current = (remainder << 8) | u16::from(*byte);
+            *byte = u8::try_from(current / 10).unwrap_or(u8::MAX);
+            remainder = current % 10;
+        }
+        digits.push(char::from_digit(u32::from(remainder), 10).unwrap_or('0'));
+    }
+    if digits.is_empty() {
+        return "0".to_owned();
+    }
+    digits.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod literal_tests {
+    use super::{field_to_decimal, LiteralError};
+    use crate::{
+        helpers,
+        jaleo::{StructMembersMap, UserInputValueType, Visibility},
+        MAX_STRING_BYTES,
+    };
+    use ark_ff::{One, UniformRand, Zero};
+    use simpleworks::gadgets::ConstraintF;
+    use snarkvm::prelude::{Group, Scalar, Testnet3};
+
+    const ADDRESS: &str = "aleo1ecw94zggphqkpdsjhfjutr9p33nn9tk2d34tz23t29awtejupugq4vne6m";
+
+    fn assert_round_trip(value: UserInputValueType) {
+        let displayed = value.to_string();
+        assert_eq!(
+            UserInputValueType::parse_literal(&displayed).unwrap(),
+            (value.clone(), None),
+            "{displayed}"
+        );
+        assert_eq!(
+            UserInputValueType::try_from(displayed.clone()).unwrap(),
+            value,
+            "{displayed}"
+        );
+        for visibility in [
+            Visibility::Constant,
+            Visibility::Public,
+            Visibility::Private,
+        ] {
+            assert_eq!(
+                UserInputValueType::parse_literal(&format!("{displayed}.{visibility}")).unwrap(),
+                (value.clone(), Some(visibility)),
+                "{displayed}.{visibility}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_integers_round_trip() {
+        for value in [u8::MIN, 1, u8::MAX] {
+            assert_round_trip(UserInputValueType::U8(value));
+        }
+        for value in [u16::MIN, 1, u16::MAX] {
+            assert_round_trip(UserInputValueType::U16(value));
+        }
+        for value in [u32::MIN, 1, u32::MAX] {
+            assert_round_trip(UserInputValueType::U32(value));
+        }
+        for value in [u64::MIN, 1, u64::MAX] {
+            assert_round_trip(UserInputValueType::U64(value));
+        }
+        for value in [u128::MIN, 1, u128::MAX] {
+            assert_round_trip(UserInputValueType::U128(value));
+        }
+        for value in [i8::MIN, -1, 0, 1, i8::MAX] {
+            assert_round_trip(UserInputValueType::I8(value));
+        }
+    }
+
+    #[test]
+    fn test_fields_round_trip() {
+        let rng = &mut rand::thread_rng();
+        assert_round_trip(UserInputValueType::Field(ConstraintF::zero()));
+        assert_round_trip(UserInputValueType::Field(ConstraintF::one()));
+        assert_round_trip(UserInputValueType::Field(-ConstraintF::one()));
+        for _ in 0..10 {
+            assert_round_trip(UserInputValueType::Field(ConstraintF::rand(rng)));
+        }
+        assert_eq!(field_to_decimal(&ConstraintF::from(1234_u64)), "1234");
+    }
+
+    #[test]
+    fn test_groups_and_scalars_round_trip() {
+        let rng = &mut rand::thread_rng();
+        assert_round_trip(UserInputValueType::Group(helpers::random_nonce()));
+        assert_round_trip(UserInputValueType::Group(Group::<Testnet3>::generator()));
+        assert_round_trip(UserInputValueType::Scalar(Scalar::<Testnet3>::rand(rng)));
+    }
+
+    #[test]
+    fn test_other_literals_round_trip() {
+        assert_round_trip(UserInputValueType::Boolean(true));
+        assert_round_trip(UserInputValueType::Boolean(false));
+        assert_round_trip(UserInputValueType::Address(helpers::to_address(
+            ADDRESS.to_owned(),
+        )));
+        assert_round_trip(UserInputValueType::String(String::new()));
+        assert_round_trip(UserInputValueType::String("hello world.private".to_owned()));
+        assert_round_trip(UserInputValueType::String("a".repeat(MAX_STRING_BYTES)));
+    }
+
+    #[test]
+    fn test_structs_round_trip() {
+        let mut nested = StructMembersMap::new();
+        nested.insert("c".to_owned(), UserInputValueType::I8(-3));
+        nested.insert(
+            "d".to_owned(),
+            UserInputValueType::Field(ConstraintF::from(7_u64)),
+        );
+        let mut members = StructMembersMap::new();
+        members.insert(
+            "a".to_owned(),
+            UserInputValueType::Address(helpers::to_address(ADDRESS.to_owned())),
+        );
+        members.insert("b".to_owned(), UserInputValueType::Struct(nested));
+        let value = UserInputValueType::Struct(members);
+
+        assert_eq!(
+            UserInputValueType::try_from(value.to_string()).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn test_accepted_spellings() {
+        assert_eq!(
+            UserInputValueType::parse_literal(" 1_000u32 ").unwrap(),
+            (UserInputValueType::U32(1000), None)
+        );
+        assert_eq!(
+            UserInputValueType::parse_literal("007field").unwrap(),
+            (UserInputValueType::Field(ConstraintF::from(7_u64)), None)
+        );
+        assert_eq!(
+            UserInputValueType::parse_literal("\"hi\".public").unwrap(),
+            (
+                UserInputValueType::String("hi".to_owned()),
+                Some(Visibility::Public)
+            )
+        );
+    }
+
+    #[test]
+    fn test_literal_errors() {
+        let error = |literal: &str| UserInputValueType::parse_literal(literal).unwrap_err();
+
+        assert_eq!(error(""), LiteralError::Empty);
+        assert!(matches!(error("5"), LiteralError::UnknownType(_)));
+        assert!(matches!(error("5u7"), LiteralError::UnknownType(_)));
+        assert!(matches!(error("hello"), LiteralError::UnknownType(_)));
+        assert!(matches!(error("5i16"), LiteralError::UnsupportedType(_)));
+        assert!(matches!(error("256u8"), LiteralError::InvalidNumber { .. }));
+        assert!(matches!(error("-1u8"), LiteralError::InvalidNumber { .. }));
+        assert!(matches!(error("128i8"), LiteralError::InvalidNumber { .. }));
+        assert!(matches!(
+            error("-1field"),
+            LiteralError::InvalidNumber { .. }
+        ));
+        assert!(matches!(
+            error(&format!("{}field", "9".repeat(100))),
+            LiteralError::InvalidNumber { .. }
+        ));
+        assert!(matches!(error("aleo1abc"), LiteralError::InvalidAddress(_)));
+        assert!(matches!(error("-group"), LiteralError::InvalidGroup { .. }));
+        assert!(matches!(
+            error("\"unterminated"),
+            LiteralError::InvalidString(_)
+        ));
+        assert!(matches!(error("\"a\"b\""), LiteralError::InvalidString(_)));
+        assert_eq!(
+            error(&format!("\"{}\"", "a".repeat(MAX_STRING_BYTES + 1))),
+            LiteralError::StringTooLong(MAX_STRING_BYTES + 1)
+        );
+        assert_eq!(
+            error("5u8.secret"),
+            LiteralError::InvalidVisibility("secret".to_owned())
+        );
+    }
+}
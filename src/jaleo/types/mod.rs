@@ -3,7 +3,17 @@ use indexmap::IndexMap;
 mod user_input;
 pub use user_input::UserInputValueType;
 
+mod literal;
+pub use literal::LiteralError;
+
+mod plaintext;
+pub use plaintext::Visibility;
+
+mod record_entry;
+pub use record_entry::RecordEntry;
+
 mod serialize;
 
 pub type Address = [u8; 63];
-pub type RecordEntriesMap = IndexMap<String, UserInputValueType>;
+pub type RecordEntriesMap = IndexMap<String, RecordEntry>;
+pub type StructMembersMap = IndexMap<String, UserInputValueType>;
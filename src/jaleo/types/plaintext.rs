@@ -0,0 +1,317 @@
+//! Aleo's plaintext syntax for records, e.g.
+//! `{ owner: aleo1....private, gates: 5u64.private, amount: 3u64.private, _nonce: 0group.public }`.
+
+use super::{
+    user_input::split_top_level, RecordEntriesMap, RecordEntry, StructMembersMap,
+    UserInputValueType,
+};
+use crate::jaleo::Record;
+use anyhow::{anyhow, bail, ensure, Result};
+use indexmap::IndexMap;
+use std::{fmt, str::FromStr};
+
+/// Visibility of a record entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visibility {
+    Constant,
+    Public,
+    Private,
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Visibility::Constant => write!(f, "constant"),
+            Visibility::Public => write!(f, "public"),
+            Visibility::Private => write!(f, "private"),
+        }
+    }
+}
+
+impl FromStr for Visibility {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "constant" => Ok(Visibility::Constant),
+            "public" => Ok(Visibility::Public),
+            "private" => Ok(Visibility::Private),
+            _ => bail!("Invalid visibility `{s}`, expected constant, public or private"),
+        }
+    }
+}
+
+// Whether the value looks like a record written with Aleo's syntax, that is,
+// entries enclosed in braces with an owner.
+pub(super) fn is_record_plaintext(value: &str) -> bool {
+    match value
+        .trim()
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+    {
+        Some(inner) => split_top_level(inner, ',').iter().any(
+            |entry| matches!(entry.split_once(':'), Some((name, _)) if name.trim() == "owner"),
+        ),
+        None => false,
+    }
+}
+
+// Splits the entries of a record or struct into their names and values.
+fn split_entries(value: &str) -> Result<IndexMap<String, &str>> {
+    let inner = value
+        .trim()
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+        .ok_or_else(|| anyhow!("Records must be enclosed in braces: {value}"))?;
+
+    let mut entries = IndexMap::new();
+    for entry in split_top_level(inner, ',') {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let (name, entry_value) = entry
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid record entry `{entry}`, expected `name: value`"))?;
+        let name = name.trim();
+        ensure!(
+            !entries.contains_key(name),
+            "Duplicate record entry `{name}`"
+        );
+        entries.insert(name.to_owned(), entry_value.trim());
+    }
+    Ok(entries)
+}
+
+// Structs inside records have a visibility for each of their members, which
+// have to agree as the entry has a single visibility.
+fn parse_entry_value(value: &str) -> Result<(UserInputValueType, Option<Visibility>)> {
+    if !value.starts_with('{') {
+        return Ok(UserInputValueType::parse_literal(value)?);
+    }
+
+    let mut members = StructMembersMap::new();
+    let mut struct_visibility = None;
+    for (name, member) in split_entries(value)? {
+        let (member, visibility) = parse_entry_value(member)?;
+        if let (Some(struct_visibility), Some(visibility)) = (struct_visibility, visibility) {
+            ensure!(
+                struct_visibility == visibility,
+                "Members of the struct entry have different visibilities: {value}"
+            );
+        }
+        struct_visibility = struct_visibility.or(visibility);
+        members.insert(name, member);
+    }
+    Ok((UserInputValueType::Struct(members), struct_visibility))
+}
+
+fn format_value(
+    value: &UserInputValueType,
+    visibility: Visibility,
+    indent: usize,
+) -> Result<String> {
+    match value {
+        UserInputValueType::Struct(members) => {
+            let padding = "  ".repeat(indent + 1);
+            let mut formatted_members = Vec::with_capacity(members.len());
+            for (name, member) in members {
+                formatted_members.push(format!(
+                    "{padding}{name}: {}",
+                    format_value(member, visibility, indent + 1)?
+                ));
+            }
+            Ok(format!(
+                "{{\n{}\n{}}}",
+                formatted_members.join(",\n"),
+                "  ".repeat(indent)
+            ))
+        }
+        UserInputValueType::Record(_) => bail!("Records cannot be nested in records"),
+        _ => Ok(format!("{value}.{visibility}")),
+    }
+}
+
+impl Record {
+    /// Parses a record written with Aleo's syntax, e.g.
+    /// `{ owner: aleo1....private, gates: 5u64.private, amount: 3u64.private, _nonce: 0group.public }`.
+    /// Entries without a visibility are taken as private, the nonce is optional.
+    /// The owner and gates are always private, so any other visibility is an
+    /// error.
+    pub fn from_plaintext(plaintext: &str) -> Result<Record> {
+        let mut entries = split_entries(plaintext)?;
+
+        let nonce = match entries.shift_remove("_nonce") {
+            Some(nonce) => match parse_entry_value(nonce)?.0 {
+                UserInputValueType::Group(nonce) => Some(nonce),
+                other => bail!("The record nonce must be a group, got {other}"),
+            },
+            None => None,
+        };
+        let mut entries = entries
+            .into_iter()
+            .map(|(name, entry)| {
+                let (value, visibility) = parse_entry_value(entry)?;
+                let entry = RecordEntry::new(value, visibility.unwrap_or(Visibility::Private));
+                Ok((name, entry))
+            })
+            .collect::<Result<RecordEntriesMap>>()?;
+
+        for name in ["owner", "gates"] {
+            if let Some(entry) = entries.get(name) {
+                ensure!(
+                    entry.visibility == Visibility::Private,
+                    "The record {name} must be private, got {}",
+                    entry.visibility
+                );
+            }
+        }
+        let owner = match entries.shift_remove("owner").map(|entry| entry.value) {
+            Some(UserInputValueType::Address(owner)) => owner,
+            Some(other) => bail!("The record owner must be an address, got {other}"),
+            None => bail!("The record has no owner"),
+        };
+        let gates = match entries.shift_remove("gates").map(|entry| entry.value) {
+            Some(UserInputValueType::U64(gates)) => gates,
+            Some(other) => bail!("The record gates must be a u64, got {other}"),
+            None => bail!("The record has no gates"),
+        };
+
+        Ok(Record::new(owner, gates, entries, nonce))
+    }
+
+    /// Prints the record with Aleo's syntax, one entry per line. The owner and
+    /// gates are private and the nonce public, as in snarkVM.
+    pub fn to_plaintext(&self) -> Result<String> {
+        let mut entries = vec![
+            format!(
+                "  owner: {}",
+                format_value(
+                    &UserInputValueType::Address(self.owner),
+                    Visibility::Private,
+                    1
+                )?
+            ),
+            format!("  gates: {}u64.{}", self.gates, Visibility::Private),
+        ];
+        for (name, entry) in &self.data {
+            entries.push(format!(
+                "  {name}: {}",
+                format_value(&entry.value, entry.visibility, 1)?
+            ));
+        }
+        if let Some(nonce) = self.nonce {
+            entries.push(format!("  _nonce: {nonce}.{}", Visibility::Public));
+        }
+        Ok(format!("{{\n{}\n}}", entries.join(",\n")))
+    }
+}
+
+#[cfg(test)]
+mod plaintext_tests {
+    use super::Visibility;
+    use crate::{
+        helpers,
+        jaleo::{Record, RecordEntriesMap, RecordEntry, StructMembersMap, UserInputValueType},
+    };
+    use std::str::FromStr;
+
+    const OWNER: &str = "aleo1ecw94zggphqkpdsjhfjutr9p33nn9tk2d34tz23t29awtejupugq4vne6m";
+
+    #[test]
+    fn test_parse_record_plaintext() {
+        let nonce = helpers::random_nonce();
+        let plaintext = format!(
+            "{{ owner: {OWNER}.private, gates: 5u64.private, amount: 3u64.private, _nonce: {nonce}.public }}"
+        );
+
+        let record = Record::from_plaintext(&plaintext).unwrap();
+
+        assert_eq!(record.owner, helpers::to_address(OWNER.to_owned()));
+        assert_eq!(record.gates, 5);
+        assert_eq!(
+            record.data.get("amount"),
+            Some(&RecordEntry::from(UserInputValueType::U64(3)))
+        );
+        assert_eq!(record.nonce, Some(nonce));
+        assert_eq!(
+            UserInputValueType::try_from(plaintext).unwrap(),
+            UserInputValueType::Record(record)
+        );
+    }
+
+    #[test]
+    fn test_record_plaintext_round_trip() {
+        let mut meta = StructMembersMap::new();
+        meta.insert("expiry".to_owned(), UserInputValueType::U32(10));
+        meta.insert("flag".to_owned(), UserInputValueType::Boolean(true));
+        let mut data = RecordEntriesMap::new();
+        data.insert(
+            "amount".to_owned(),
+            RecordEntry::public(UserInputValueType::U64(3)),
+        );
+        data.insert("meta".to_owned(), UserInputValueType::Struct(meta).into());
+        let record = Record::new(
+            helpers::to_address(OWNER.to_owned()),
+            5,
+            data,
+            Some(helpers::random_nonce()),
+        );
+
+        let plaintext = record.to_plaintext().unwrap();
+
+        assert!(plaintext.contains(&format!("owner: {OWNER}.private")));
+        assert!(plaintext.contains("amount: 3u64.public"));
+        assert!(plaintext.contains("    expiry: 10u32.private"));
+        assert_eq!(Record::from_plaintext(&plaintext).unwrap(), record);
+    }
+
+    #[test]
+    fn test_record_plaintext_without_nonce_and_visibilities() {
+        let record = Record::from_plaintext(&format!("{{ owner: {OWNER}, gates: 0u64 }}")).unwrap();
+
+        assert_eq!(record.nonce, None);
+        assert!(record.data.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_record_plaintext() {
+        assert!(Record::from_plaintext("{ gates: 0u64.private }").is_err());
+        assert!(Record::from_plaintext(&format!("{{ owner: {OWNER}.private }}")).is_err());
+        assert!(Record::from_plaintext(&format!(
+            "{{ owner: {OWNER}.private, gates: 0u8.private }}"
+        ))
+        .is_err());
+        assert!(Record::from_plaintext(&format!(
+            "{{ owner: {OWNER}.private, gates: 0u64.private, gates: 1u64.private }}"
+        ))
+        .is_err());
+        assert!(Record::from_plaintext(&format!(
+            "{{ owner: {OWNER}.private, gates: 0u64.private, meta: {{ a: 1u8.public, b: 2u8.private }} }}"
+        ))
+        .is_err());
+        assert!(Record::from_plaintext(&format!(
+            "{{ owner: {OWNER}.public, gates: 0u64.private }}"
+        ))
+        .is_err());
+        assert!(Record::from_plaintext(&format!(
+            "{{ owner: {OWNER}.private, gates: 0u64.constant }}"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_visibility_round_trip() {
+        for visibility in [
+            Visibility::Constant,
+            Visibility::Public,
+            Visibility::Private,
+        ] {
+            assert_eq!(
+                Visibility::from_str(&visibility.to_string()).unwrap(),
+                visibility
+            );
+        }
+        assert!(Visibility::from_str("secret").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use super::{UserInputValueType, Visibility};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::{EntryType, Testnet3};
+use std::{fmt, str::FromStr};
+
+/// An entry of a record along with its visibility. Public and constant
+/// entries are left in plaintext when the record is encrypted and public
+/// ones are public inputs of the circuits that use the record.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct RecordEntry {
+    pub value: UserInputValueType,
+    pub visibility: Visibility,
+}
+
+impl RecordEntry {
+    pub fn new(value: UserInputValueType, visibility: Visibility) -> Self {
+        Self { value, visibility }
+    }
+
+    pub fn public(value: UserInputValueType) -> Self {
+        Self::new(value, Visibility::Public)
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.visibility == Visibility::Private
+    }
+}
+
+/// Entries are private unless stated otherwise.
+impl From<UserInputValueType> for RecordEntry {
+    fn from(value: UserInputValueType) -> Self {
+        Self::new(value, Visibility::Private)
+    }
+}
+
+impl From<&EntryType<Testnet3>> for Visibility {
+    fn from(entry_type: &EntryType<Testnet3>) -> Self {
+        match entry_type {
+            EntryType::Constant(_) => Visibility::Constant,
+            EntryType::Public(_) => Visibility::Public,
+            EntryType::Private(_) => Visibility::Private,
+        }
+    }
+}
+
+impl TryFrom<String> for RecordEntry {
+    type Error = anyhow::Error;
+
+    /// Parses the entry value followed by an optional visibility, e.g.
+    /// `3u64.public` or `{ a: 1u8 }.constant`.
+    fn try_from(entry: String) -> Result<Self> {
+        let visibility_suffix = entry.rsplit_once('.').and_then(|(value, visibility)| {
+            Visibility::from_str(visibility)
+                .ok()
+                .map(|visibility| (value, visibility))
+        });
+        match visibility_suffix {
+            Some((value, visibility)) => Ok(Self::new(
+                UserInputValueType::try_from(value.to_owned())?,
+                visibility,
+            )),
+            None => Ok(Self::from(UserInputValueType::try_from(entry)?)),
+        }
+    }
+}
+
+// Private entries are written without their visibility so records with only
+// private entries keep their former serialization, and with it their
+// commitments.
+impl fmt::Display for RecordEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.visibility {
+            Visibility::Private => write!(f, "{}", self.value),
+            visibility => write!(f, "{}.{visibility}", self.value),
+        }
+    }
+}
+
+impl Serialize for RecordEntry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.to_string().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod record_entry_tests {
+    use super::RecordEntry;
+    use crate::jaleo::{StructMembersMap, UserInputValueType, Visibility};
+
+    #[test]
+    fn test_record_entry_round_trip() {
+        let mut members = StructMembersMap::new();
+        members.insert("a".to_owned(), UserInputValueType::U8(1));
+        let entries = [
+            RecordEntry::from(UserInputValueType::U64(3)),
+            RecordEntry::public(UserInputValueType::U64(3)),
+            RecordEntry::new(UserInputValueType::Struct(members), Visibility::Constant),
+            RecordEntry::public(UserInputValueType::String("a.public".to_owned())),
+        ];
+
+        for entry in entries {
+            let serialized = serde_json::to_string(&entry).unwrap();
+            assert_eq!(
+                serde_json::from_str::<RecordEntry>(&serialized).unwrap(),
+                entry
+            );
+        }
+    }
+
+    #[test]
+    fn test_record_entry_defaults_to_private() {
+        let entry = RecordEntry::try_from("3u64".to_owned()).unwrap();
+
+        assert!(entry.is_private());
+        assert_eq!(entry.to_string(), "3u64");
+        assert_eq!(
+            RecordEntry::try_from("3u64.public".to_owned()).unwrap(),
+            RecordEntry::public(UserInputValueType::U64(3))
+        );
+        assert!(RecordEntry::try_from("3u64.secret".to_owned()).is_err());
+    }
+}
@@ -1,17 +1,21 @@
+use super::{
+    literal::field_to_decimal, plaintext::is_record_plaintext, RecordEntriesMap, StructMembersMap,
+    Visibility,
+};
 use crate::helpers;
 use crate::jaleo::Record as JAleoRecord;
-use anyhow::{anyhow, bail, Result};
+use crate::MAX_STRING_BYTES;
+use anyhow::{anyhow, bail, ensure, Result};
 use ark_ff::ToConstraintField;
-use indexmap::IndexMap;
 use serde::ser::Error;
 use serde::Deserialize;
 use simpleworks::gadgets::traits::ToFieldElements;
 use simpleworks::gadgets::ConstraintF;
+use snarkvm::prelude::{Group, Scalar, Testnet3};
 use std::str::FromStr;
 use std::{convert::TryFrom, fmt};
 
 pub type Address = [u8; 63];
-pub type RecordEntriesMap = IndexMap<String, UserInputValueType>;
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 #[serde(try_from = "String")]
@@ -26,6 +30,10 @@ pub enum UserInputValueType {
     Record(JAleoRecord),
     Boolean(bool),
     Field(ConstraintF),
+    Struct(StructMembersMap),
+    String(String),
+    Group(Group<Testnet3>),
+    Scalar(Scalar<Testnet3>),
 }
 
 fn hashmap_to_string(hashmap: &RecordEntriesMap) -> Result<String> {
@@ -43,6 +51,68 @@ fn hashmap_to_string(hashmap: &RecordEntriesMap) -> Result<String> {
     Ok(ret)
 }
 
+// Formats the struct members with Aleo's syntax, e.g. `{ a: 1u8, b: 2u8 }`.
+fn struct_to_string(members: &StructMembersMap) -> Result<String> {
+    let mut formatted_members = Vec::with_capacity(members.len());
+    for (name, member) in members {
+        formatted_members.push(format!("{name}: {member}"));
+    }
+    Ok(format!("{{ {} }}", formatted_members.join(", ")))
+}
+
+// Parses a struct written with Aleo's syntax, e.g. `{ a: 1u8, b: { c: true } }`.
+fn parse_struct_members(value: &str) -> Result<StructMembersMap> {
+    let inner = value
+        .trim()
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+        .ok_or_else(|| anyhow!("Structs must be enclosed in braces: {value}"))?;
+
+    let mut members = StructMembersMap::new();
+    for member in split_top_level(inner, ',') {
+        let member = member.trim();
+        if member.is_empty() {
+            continue;
+        }
+        let (name, member_value) = member
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid struct member `{member}`, expected `name: value`"))?;
+        let name = name.trim();
+        ensure!(
+            !members.contains_key(name),
+            "Duplicate struct member `{name}`"
+        );
+        members.insert(
+            name.to_owned(),
+            UserInputValueType::try_from(member_value.trim().to_owned())?,
+        );
+    }
+    Ok(members)
+}
+
+// Splits the given string by the separator, ignoring the separators that are
+// inside nested braces or strings.
+pub(super) fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0_usize;
+    let mut in_string = false;
+    let mut start = 0_usize;
+    for (index, character) in value.char_indices() {
+        match character {
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => depth = depth.saturating_sub(1),
+            c if c == separator && depth == 0 && !in_string => {
+                parts.push(value.get(start..index).unwrap_or_default());
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(value.get(start..).unwrap_or_default());
+    parts
+}
+
 impl From<UserInputValueType> for String {
     fn from(value: UserInputValueType) -> Self {
         format!("{value}")
@@ -62,46 +132,17 @@ impl TryFrom<String> for UserInputValueType {
     type Error = anyhow::Error;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        if value.ends_with("u8") {
-            let v = value.trim_end_matches("u8");
-            let value_int = v.parse::<u8>().map_err(|e| anyhow!("{}", e))?;
-            Ok(UserInputValueType::U8(value_int))
-        } else if value.ends_with("u16") {
-            let v = value.trim_end_matches("u16");
-            let value_int = v.parse::<u16>().map_err(|e| anyhow!("{}", e))?;
-            Ok(UserInputValueType::U16(value_int))
-        } else if value.ends_with("u32") {
-            let v = value.trim_end_matches("u32");
-            let value_int = v.parse::<u32>().map_err(|e| anyhow!("{}", e))?;
-            Ok(UserInputValueType::U32(value_int))
-        } else if value.ends_with("u64") {
-            let v = value.trim_end_matches("u64");
-            let value_int = v.parse::<u64>().map_err(|e| anyhow!("{}", e))?;
-            Ok(UserInputValueType::U64(value_int))
-        } else if value.ends_with("u128") {
-            let v = value.trim_end_matches("u128");
-            let value_int = v.parse::<u128>().map_err(|e| anyhow!("{}", e))?;
-            Ok(UserInputValueType::U128(value_int))
-        } else if value.starts_with("aleo1") {
-            let mut address = [0_u8; 63];
-            for (sender_address_byte, address_string_byte) in
-                address.iter_mut().zip(value.as_bytes())
-            {
-                *sender_address_byte = *address_string_byte;
-            }
-            Ok(UserInputValueType::Address(address))
-        } else if value == "true" {
-            Ok(UserInputValueType::Boolean(true))
-        } else if value == "false" {
-            Ok(UserInputValueType::Boolean(false))
-        } else if value.ends_with("field") {
-            let v = value.trim_end_matches("field");
-            let value_int = v.parse::<ConstraintF>().map_err(|e| anyhow!("{:?}", e))?;
-            Ok(UserInputValueType::Field(value_int))
-        } else {
-            // This is the Record case, we expect it to be json
-            let record = serde_json::from_str::<JAleoRecord>(&value)?;
-            Ok(UserInputValueType::Record(record))
+        if !value.trim_start().starts_with('{') {
+            return Ok(UserInputValueType::parse_literal(&value)?.0);
+        }
+        // This is either the Record case, which we expect to be json or
+        // written with Aleo's syntax, or a struct written with Aleo's syntax.
+        match serde_json::from_str::<JAleoRecord>(&value) {
+            Ok(record) => Ok(UserInputValueType::Record(record)),
+            Err(_) if is_record_plaintext(&value) => Ok(UserInputValueType::Record(
+                JAleoRecord::from_plaintext(&value)?,
+            )),
+            Err(_) => Ok(UserInputValueType::Struct(parse_struct_members(&value)?)),
         }
     }
 }
@@ -118,7 +159,7 @@ impl fmt::Display for UserInputValueType {
             UserInputValueType::Address(v) => {
                 write!(
                     f,
-                    "{:?}",
+                    "{}",
                     helpers::bytes_to_string(v).map_err(fmt::Error::custom)?
                 )
             }
@@ -143,7 +184,19 @@ impl fmt::Display for UserInputValueType {
                 )
             }
             UserInputValueType::Boolean(b) => write!(f, "{b}"),
-            UserInputValueType::Field(field_element) => write!(f, "{field_element}"),
+            UserInputValueType::Field(field_element) => {
+                write!(f, "{}field", field_to_decimal(field_element))
+            }
+            UserInputValueType::Struct(members) => {
+                write!(
+                    f,
+                    "{}",
+                    struct_to_string(members).map_err(fmt::Error::custom)?
+                )
+            }
+            UserInputValueType::String(string) => write!(f, "\"{string}\""),
+            UserInputValueType::Group(group) => write!(f, "{group}"),
+            UserInputValueType::Scalar(scalar) => write!(f, "{scalar}"),
         }
     }
 }
@@ -158,18 +211,41 @@ impl ToFieldElements<ConstraintF> for UserInputValueType {
             UserInputValueType::U64(value) => value.to_field_elements(),
             UserInputValueType::U128(value) => value.to_field_elements(),
             UserInputValueType::Address(value) => value.to_field_elements(),
-            UserInputValueType::Record(JAleoRecord {
-                owner: _,
-                gates: _,
-                data: _,
-                nonce: _,
-            }) => {
-                bail!("Converting records to field elements is not supported")
+            // Only the public entries of a record are public inputs.
+            UserInputValueType::Record(record) => {
+                let mut field_elements = Vec::new();
+                for entry in record.data.values() {
+                    if entry.visibility == Visibility::Public {
+                        field_elements.extend(entry.value.to_field_elements()?);
+                    }
+                }
+                Ok(field_elements)
             }
             UserInputValueType::Boolean(b) => b
                 .to_field_elements()
                 .ok_or_else(|| anyhow!("Error turning bool to field elements")),
             UserInputValueType::Field(field_element) => Ok(vec![*field_element]),
+            UserInputValueType::Struct(members) => {
+                let mut field_elements = Vec::new();
+                for member in members.values() {
+                    field_elements.extend(member.to_field_elements()?);
+                }
+                Ok(field_elements)
+            }
+            // Strings are public inputs as their length followed by their
+            // bytes padded up to MAX_STRING_BYTES.
+            UserInputValueType::String(string) => {
+                let mut bytes = string.as_bytes().to_vec();
+                bytes.resize(MAX_STRING_BYTES, 0);
+                let mut field_elements = u8::try_from(string.len())?.to_field_elements()?;
+                for byte in bytes {
+                    field_elements.extend(byte.to_field_elements()?);
+                }
+                Ok(field_elements)
+            }
+            UserInputValueType::Group(_) | UserInputValueType::Scalar(_) => {
+                bail!("Converting groups and scalars to field elements is not supported")
+            }
         }
     }
 }
@@ -178,7 +254,7 @@ impl ToFieldElements<ConstraintF> for UserInputValueType {
 mod tests {
     use crate::{
         helpers,
-        jaleo::{Record, RecordEntriesMap},
+        jaleo::{Record, RecordEntriesMap, StructMembersMap},
     };
 
     use super::UserInputValueType;
@@ -212,7 +288,7 @@ mod tests {
         }
         let v = UserInputValueType::Address(address);
         let out = format!("{v}");
-        assert_eq!(out, format!("\"{address_str}\""));
+        assert_eq!(out, address_str);
         // Record
         let mut address = [0_u8; 63];
         let address_str = "aleo1ecw94zggphqkpdsjhfjutr9p33nn9tk2d34tz23t29awtejupugq4vne6m";
@@ -299,7 +375,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_struct() {
+        let v: UserInputValueType =
+            serde_json::from_str("\"{ a: 1u8, b: { c: true, d: 2u64 } }\"").unwrap();
+
+        assert!(matches!(v, UserInputValueType::Struct(_)));
+        if let UserInputValueType::Struct(members) = v {
+            assert_eq!(members.get("a").unwrap(), &UserInputValueType::U8(1));
+            assert!(matches!(
+                members.get("b").unwrap(),
+                UserInputValueType::Struct(_)
+            ));
+            if let Some(UserInputValueType::Struct(nested_members)) = members.get("b") {
+                assert_eq!(
+                    nested_members.get("c").unwrap(),
+                    &UserInputValueType::Boolean(true)
+                );
+                assert_eq!(
+                    nested_members.get("d").unwrap(),
+                    &UserInputValueType::U64(2)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_deserialize_struct_with_duplicated_members() {
+        assert!(serde_json::from_str::<UserInputValueType>("\"{ a: 1u8, a: 2u8 }\"").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_string() {
+        let v: UserInputValueType = serde_json::from_str(r#""\"hello world\"""#).unwrap();
+
+        assert_eq!(v, UserInputValueType::String("hello world".to_owned()));
+    }
+
+    #[test]
+    fn test_deserialize_string_that_is_too_long() {
+        let data = format!(r#""\"{}\"""#, "a".repeat(256));
+
+        assert!(serde_json::from_str::<UserInputValueType>(&data).is_err());
+    }
+
     /* Serialize Tests */
+    #[test]
+    fn test_serialize_string() {
+        let data = UserInputValueType::String("hello world".to_owned());
+
+        let v = serde_json::to_string(&data).unwrap();
+
+        assert_eq!(v, r#""\"hello world\"""#);
+    }
+
+    #[test]
+    fn test_serialize_struct() {
+        let address = "aleo1ecw94zggphqkpdsjhfjutr9p33nn9tk2d34tz23t29awtejupugq4vne6m";
+        let mut members = StructMembersMap::new();
+        members.insert("a".to_owned(), UserInputValueType::U8(1));
+        members.insert(
+            "b".to_owned(),
+            UserInputValueType::Address(helpers::to_address(address.to_owned())),
+        );
+        let data = UserInputValueType::Struct(members);
+
+        let v = serde_json::to_string(&data).unwrap();
+
+        assert_eq!(v, format!("\"{{ a: 1u8, b: {address} }}\""));
+        assert_eq!(
+            serde_json::from_str::<UserInputValueType>(&v).unwrap(),
+            data
+        );
+    }
+
     #[test]
     fn test_serialize_address() {
         let mut address = [0_u8; 63];
@@ -395,7 +544,7 @@ mod tests {
             *sender_address_byte = *address_string_byte;
         }
         let mut data = RecordEntriesMap::new();
-        data.insert("amount".to_owned(), UserInputValueType::U64(0));
+        data.insert("amount".to_owned(), UserInputValueType::U64(0).into());
         let rng = &mut rand::thread_rng();
         let randomizer = Scalar::rand(rng);
         let nonce = Group::generator() * randomizer;
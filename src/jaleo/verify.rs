@@ -0,0 +1,123 @@
+use super::{
+    deserialize_proof, Deployment, Program, RecordEntriesMap, Transition, UserInputValueType,
+    Visibility,
+};
+use crate::{universal_srs::load_universal_srs_from_file, variable_type::VariableType};
+use anyhow::{anyhow, ensure, Result};
+use snarkvm::prelude::{Function, Testnet3, ValueType};
+
+/// Verifies the proof of a transition against the verifying key of its
+/// function in the given deployment.
+///
+/// The public inputs of the proof are the public inputs of the transition
+/// along with the public entries of its input records, in order, followed by
+/// the public entries of its output records. If the function has constant
+/// inputs, the verifying key is rebuilt for the constants of the transition
+/// instead of taking the deployed one, which was built with default constants.
+pub fn verify_execution(transition: &Transition, deployment: &Deployment) -> Result<bool> {
+    verify_execution_with_imports(transition, deployment, &[])
+}
+
+/// Same as `verify_execution` but for programs that import others, which are
+/// needed to rebuild the verifying key of functions with constant inputs.
+pub fn verify_execution_with_imports(
+    transition: &Transition,
+    deployment: &Deployment,
+    imports: &[Program],
+) -> Result<bool> {
+    ensure!(
+        transition.program_id() == deployment.program.id(),
+        "Transition is for program {} but the deployment is for {}",
+        transition.program_id(),
+        deployment.program.id()
+    );
+
+    let function = deployment
+        .program
+        .get_function(transition.function_name())?;
+    check_transition_inputs(&function, transition)?;
+
+    let mut public_inputs = Vec::new();
+    for input in &transition.inputs {
+        match input {
+            VariableType::Public(value) => public_inputs.push(value.clone()),
+            VariableType::Record(_, record) => {
+                public_inputs.extend(public_entries(&record.data));
+            }
+            _ => {}
+        }
+    }
+    for output in &transition.outputs {
+        if let VariableType::EncryptedRecord((_commitment, encrypted_record)) = output {
+            public_inputs.extend(public_entries(&encrypted_record.public_entries));
+        }
+    }
+    let proof = deserialize_proof(hex::decode(&transition.proof)?)?;
+
+    let constants = transition.constant_inputs();
+    if constants.is_empty() {
+        let verifying_key = deployment
+            .verifying_keys
+            .map
+            .get(transition.function_name())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Function {} not found in the deployment of {}",
+                    transition.function_name(),
+                    deployment.program.id()
+                )
+            })?;
+        crate::verify_proof(verifying_key.clone(), &public_inputs, &proof)
+    } else {
+        let universal_srs = load_universal_srs_from_file()?;
+        crate::verify_proof_with_constants(
+            &deployment.program,
+            imports,
+            transition.function_name(),
+            &constants,
+            &public_inputs,
+            &proof,
+            &universal_srs,
+        )
+    }
+}
+
+/// Checks that the inputs of the transition are the ones its function takes,
+/// so that leaving out the constant inputs does not get the proof checked
+/// against the deployed keys, which were built with default constants.
+fn check_transition_inputs(function: &Function<Testnet3>, transition: &Transition) -> Result<()> {
+    ensure!(
+        function.inputs().len() == transition.inputs.len(),
+        "Function {} takes {} inputs but the transition has {}",
+        function.name(),
+        function.inputs().len(),
+        transition.inputs.len()
+    );
+    for (function_input, input) in function.inputs().iter().zip(&transition.inputs) {
+        let input_matches = matches!(
+            (function_input.value_type(), input),
+            (ValueType::Constant(_), VariableType::Constant(_))
+                | (ValueType::Public(_), VariableType::Public(_))
+                | (ValueType::Private(_), VariableType::Private(_))
+                | (
+                    ValueType::Record(_) | ValueType::ExternalRecord(_),
+                    VariableType::Record(..)
+                )
+        );
+        ensure!(
+            input_matches,
+            "Input {} of function {} is {} but the transition gives {input}",
+            function_input.register(),
+            function.name(),
+            function_input.value_type()
+        );
+    }
+    Ok(())
+}
+
+fn public_entries(entries: &RecordEntriesMap) -> impl Iterator<Item = UserInputValueType> + '_ {
+    entries
+        .values()
+        .filter(|entry| entry.visibility == Visibility::Public)
+        .map(|entry| entry.value.clone())
+}
@@ -32,10 +32,11 @@
     clippy::let_underscore_must_use
 )]
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef};
 use indexmap::IndexMap;
 use jaleo::{Identifier, UserInputValueType};
+use serde::Serialize;
 pub use simpleworks::marlin::serialization::{deserialize_verifying_key, serialize_verifying_key};
 use simpleworks::{
     gadgets::{
@@ -44,19 +45,28 @@ use simpleworks::{
     },
     marlin::{MarlinProof, ProvingKey, UniversalSRS, VerifyingKey},
 };
-use snarkvm::prelude::{Function, Parser, Program, Testnet3};
+use snarkvm::prelude::{Function, Parser, Program, Testnet3, ValueType};
 use std::cell::RefCell;
 use std::rc::Rc;
-use universal_srs::load_universal_srs_from_file;
+use universal_srs::{load_universal_srs_from_file, UniversalSRSBounds};
 
 mod circuit_io_type;
-pub use circuit_io_type::CircuitIOType;
+pub use circuit_io_type::{CircuitIOType, VMStructMembersMap};
 
+mod binary;
+pub use binary::BINARY_FORMAT_VERSION;
+pub mod ceremony;
+pub mod debugger;
+mod diagnostics;
+pub use diagnostics::{check_program, Diagnostic};
 pub mod helpers;
 pub mod instructions;
 pub mod jaleo;
+mod poseidon_gadget;
 mod record;
+mod string_gadget;
 pub use record::{Record, VMRecordEntriesMap};
+pub use string_gadget::{StringGadget, MAX_STRING_BYTES};
 pub use variable_type::VariableType;
 mod program_build;
 pub use program_build::ProgramBuild;
@@ -69,6 +79,81 @@ pub type CircuitInputType = IndexMap<String, variable_type::VariableType>;
 pub type SimpleFunctionVariables = IndexMap<String, Option<CircuitIOType>>;
 pub type FunctionKeys = (ProvingKey, VerifyingKey);
 
+/// Size of the circuit of a function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct ConstraintCounts {
+    pub constraints: usize,
+    /// Public variables, including the constant one.
+    pub instance_variables: usize,
+    pub witness_variables: usize,
+    /// Non zero entries of the densest of the A, B and C matrices.
+    pub non_zero: usize,
+}
+
+impl ConstraintCounts {
+    pub fn from_constraint_system(constraint_system: &ConstraintSystemRef<ConstraintF>) -> Self {
+        Self {
+            constraints: constraint_system.num_constraints(),
+            instance_variables: constraint_system.num_instance_variables(),
+            witness_variables: constraint_system.num_witness_variables(),
+            non_zero: num_non_zero(constraint_system).unwrap_or_default(),
+        }
+    }
+
+    /// Bounds the universal parameters need to have to build keys for a
+    /// circuit of this size. Marlin pads the public variables to a power of
+    /// two before indexing the circuit, so they are counted padded.
+    pub fn universal_srs_bounds(&self) -> UniversalSRSBounds {
+        UniversalSRSBounds {
+            num_constraints: self.constraints,
+            num_variables: self.instance_variables.next_power_of_two() + self.witness_variables,
+            num_non_zero: self.non_zero,
+        }
+    }
+}
+
+/// The linear combinations have to be inlined to build the matrices, which
+/// is done on a copy so the constraint system is left as it is.
+fn num_non_zero(constraint_system: &ConstraintSystemRef<ConstraintF>) -> Option<usize> {
+    let cs_clone = (*constraint_system.borrow()?).clone();
+    let cs_ref_clone = ConstraintSystemRef::CS(Rc::new(RefCell::new(cs_clone)));
+    cs_ref_clone.finalize();
+    let matrices = cs_ref_clone.to_matrices()?;
+    Some(
+        matrices
+            .a_num_non_zero
+            .max(matrices.b_num_non_zero)
+            .max(matrices.c_num_non_zero),
+    )
+}
+
+/// Processes the inputs and the instructions of a function, which adds its
+/// circuit to the constraint system.
+pub(crate) fn synthesize_function(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function: &Function<Testnet3>,
+    user_inputs: &[UserInputValueType],
+    constraint_system: &ConstraintSystemRef<ConstraintF>,
+    function_variables: &mut SimpleFunctionVariables,
+) -> Result<()> {
+    helpers::process_inputs(
+        program,
+        imports,
+        function,
+        constraint_system,
+        user_inputs,
+        function_variables,
+    )?;
+    helpers::process_outputs(
+        program,
+        imports,
+        function,
+        function_variables,
+        constraint_system.clone(),
+    )
+}
+
 /// Returns the circuit outputs and the marlin proof.
 ///
 /// # Parameters
@@ -85,20 +170,36 @@ pub fn execute_function(
     function_name: &str,
     user_inputs: &[UserInputValueType],
 ) -> Result<(SimpleFunctionVariables, MarlinProof)> {
+    let (function_variables, proof, _constraint_counts) =
+        execute_function_with_imports(program, &[], function_name, user_inputs)?;
+    Ok((function_variables, proof))
+}
+
+/// Same as `execute_function` but for programs that import others, which is
+/// needed to type check external records. It also returns the size of the
+/// circuit of the function.
+pub fn execute_function_with_imports(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function_name: &str,
+    user_inputs: &[UserInputValueType],
+) -> Result<(SimpleFunctionVariables, MarlinProof, ConstraintCounts)> {
     let rng = &mut simpleworks::marlin::generate_rand();
-    let universal_srs = simpleworks::marlin::generate_universal_srs(100000, 25000, 300000, rng)?;
     let constraint_system = ConstraintSystem::<ConstraintF>::new_ref();
     let function = program.get_function(&Identifier::try_from(function_name)?)?;
 
     let mut function_variables = helpers::function_variables(&function, constraint_system.clone())?;
-    let (function_proving_key, _function_verifying_key) = build_function(
+    synthesize_function(
         program,
+        imports,
         &function,
         user_inputs,
-        constraint_system.clone(),
-        &universal_srs,
+        &constraint_system,
         &mut function_variables,
     )?;
+    let constraint_counts = ConstraintCounts::from_constraint_system(&constraint_system);
+    let (function_proving_key, _function_verifying_key) =
+        generate_function_keys_with_stored_universal_srs(constraint_system.clone())?;
 
     // Here we clone the constraint system because deep down when generating
     // the proof the constraint system is consumed and it has to have one
@@ -112,44 +213,169 @@ pub fn execute_function(
 
     let proof = simpleworks::marlin::generate_proof(cs_ref_clone, function_proving_key, rng)?;
 
-    Ok((function_variables, proof))
+    Ok((function_variables, proof, constraint_counts))
+}
+
+/// Same as `execute_function_with_imports` but using an already generated
+/// proving key (e.g. one loaded from a `ProgramBuild` on disk) instead of
+/// generating the function keys again. Stored keys are built with default
+/// constants, so functions with constant inputs cannot use them.
+pub fn execute_function_with_proving_key(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function_name: &str,
+    user_inputs: &[UserInputValueType],
+    proving_key: ProvingKey,
+) -> Result<(SimpleFunctionVariables, MarlinProof, ConstraintCounts)> {
+    let rng = &mut simpleworks::marlin::generate_rand();
+    let constraint_system = ConstraintSystem::<ConstraintF>::new_ref();
+    let function = program.get_function(&Identifier::try_from(function_name)?)?;
+    ensure!(
+        !helpers::function_has_constant_inputs(&function),
+        "Function {function_name} has constant inputs, its proofs need keys built for the given constants instead of stored ones"
+    );
+
+    let mut function_variables = helpers::function_variables(&function, constraint_system.clone())?;
+    synthesize_function(
+        program,
+        imports,
+        &function,
+        user_inputs,
+        &constraint_system,
+        &mut function_variables,
+    )?;
+    let constraint_counts = ConstraintCounts::from_constraint_system(&constraint_system);
+
+    // See execute_function_with_imports for why the constraint system is cloned.
+    let cs_clone = (*constraint_system
+        .borrow()
+        .ok_or("Error borrowing")
+        .map_err(|e| anyhow!("{}", e))?)
+    .clone();
+    let cs_ref_clone = ConstraintSystemRef::CS(Rc::new(RefCell::new(cs_clone)));
+
+    let proof = simpleworks::marlin::generate_proof(cs_ref_clone, proving_key, rng)?;
+
+    Ok((function_variables, proof, constraint_counts))
+}
+
+/// Generates the keys of a circuit from the universal parameters trimmed to
+/// its size, so the keys of a function do not depend on how big the
+/// parameters they come from are.
+fn generate_function_keys(
+    universal_srs: &UniversalSRS,
+    constraint_system: ConstraintSystemRef<ConstraintF>,
+) -> Result<FunctionKeys> {
+    let bounds =
+        ConstraintCounts::from_constraint_system(&constraint_system).universal_srs_bounds();
+    let universal_srs = universal_srs::trim_universal_srs(universal_srs, &bounds)?;
+    simpleworks::marlin::generate_proving_and_verifying_keys(&universal_srs, constraint_system)
+}
+
+/// Same as `generate_function_keys` but with the stored universal parameters,
+/// which are the ones builds and verifiers use. Fails with the size they need
+/// to have if they are too small for the circuit.
+pub(crate) fn generate_function_keys_with_stored_universal_srs(
+    constraint_system: ConstraintSystemRef<ConstraintF>,
+) -> Result<FunctionKeys> {
+    let required_bounds =
+        ConstraintCounts::from_constraint_system(&constraint_system).universal_srs_bounds();
+    let universal_srs = load_universal_srs_fitting(&required_bounds)?;
+    generate_function_keys(&universal_srs, constraint_system)
+}
+
+/// Loads the stored universal parameters, checking from their header that
+/// they are big enough for circuits of the `required` size before reading
+/// them.
+fn load_universal_srs_fitting(required: &UniversalSRSBounds) -> Result<Box<UniversalSRS>> {
+    let (_parameters_dir, universal_srs_path) =
+        universal_srs::get_universal_srs_dir_and_filepath()?;
+    let universal_srs_bounds =
+        universal_srs::read_universal_srs_header(&universal_srs_path)?.bounds;
+    universal_srs::ensure_universal_srs_fits(&universal_srs_bounds, required)?;
+    load_universal_srs_from_file()
+}
+
+/// Synthesizes the circuit of a function for the given inputs, without
+/// generating keys nor a proof, and returns its size.
+pub fn function_constraint_counts(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function_name: &str,
+    user_inputs: &[UserInputValueType],
+) -> Result<ConstraintCounts> {
+    let constraint_system = ConstraintSystem::<ConstraintF>::new_ref();
+    let function = program.get_function(&Identifier::try_from(function_name)?)?;
+
+    let mut function_variables = helpers::function_variables(&function, constraint_system.clone())?;
+    synthesize_function(
+        program,
+        imports,
+        &function,
+        user_inputs,
+        &constraint_system,
+        &mut function_variables,
+    )?;
+
+    Ok(ConstraintCounts::from_constraint_system(&constraint_system))
 }
 
 /// Builds a program, which means generating the proving and verifying keys
-/// for each function in the program.
+/// for each function in the program. Every function is synthesized first to
+/// check that the universal parameters are big enough for all of them, and
+/// the error says which size is needed otherwise. The keys of each function
+/// are built from the parameters trimmed to its size.
 pub fn build_program(program_string: &str) -> Result<(Program<Testnet3>, ProgramBuild)> {
-    let universal_srs = load_universal_srs_from_file()?;
+    build_program_with_imports(program_string, &[])
+}
+
+/// Same as `build_program` but for programs that import others.
+pub fn build_program_with_imports(
+    program_string: &str,
+    imports: &[Program<Testnet3>],
+) -> Result<(Program<Testnet3>, ProgramBuild)> {
     let (_, program) = Program::<Testnet3>::parse(program_string).map_err(|e| anyhow!("{}", e))?;
 
-    let mut program_build = ProgramBuild {
-        map: IndexMap::new(),
+    let mut required_bounds = UniversalSRSBounds {
+        num_constraints: 0,
+        num_variables: 0,
+        num_non_zero: 0,
     };
+    let mut constraint_systems = Vec::new();
     for (function_name, function) in program.functions() {
         let constraint_system = ConstraintSystem::<ConstraintF>::new_ref();
-        let inputs = helpers::default_user_inputs(&program, function_name)?;
-        let (function_proving_key, function_verifying_key) = match build_function(
+        let inputs = helpers::default_user_inputs(&program, imports, function_name)?;
+        if let Err(e) = synthesize_function(
             &program,
+            imports,
             function,
             &inputs,
-            constraint_system.clone(),
-            &universal_srs,
+            &constraint_system,
             &mut helpers::function_variables(function, constraint_system.clone())?,
         ) {
-            Ok((function_proving_key, function_verifying_key)) => {
-                (function_proving_key, function_verifying_key)
-            }
+            bail!(
+                "Couldn't build function \"{}\": {}",
+                function_name.to_string(),
+                e
+            );
+        }
+        required_bounds = required_bounds.max(
+            ConstraintCounts::from_constraint_system(&constraint_system).universal_srs_bounds(),
+        );
+        constraint_systems.push((*function.name(), constraint_system));
+    }
+    let universal_srs = load_universal_srs_fitting(&required_bounds)?;
+    let mut program_build = ProgramBuild {
+        map: IndexMap::new(),
+    };
+    for (function_name, constraint_system) in constraint_systems {
+        let function_keys = match generate_function_keys(&universal_srs, constraint_system) {
+            Ok(function_keys) => function_keys,
             Err(e) => {
-                bail!(
-                    "Couldn't build function \"{}\": {}",
-                    function_name.to_string(),
-                    e
-                );
+                bail!("Couldn't build function \"{}\": {}", function_name, e);
             }
         };
-        program_build.map.insert(
-            *function.name(),
-            (function_proving_key, function_verifying_key),
-        );
+        program_build.map.insert(function_name, function_keys);
     }
 
     Ok((program, program_build))
@@ -164,19 +390,103 @@ pub fn build_function(
     universal_srs: &UniversalSRS,
     function_variables: &mut SimpleFunctionVariables,
 ) -> Result<FunctionKeys> {
-    helpers::process_inputs(
+    build_function_with_imports(
+        program,
+        &[],
         function,
-        &constraint_system,
         user_inputs,
+        constraint_system,
+        universal_srs,
         function_variables,
-    )?;
-    helpers::process_outputs(
+    )
+}
+
+/// Same as `build_function` but for programs that import others.
+pub fn build_function_with_imports(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function: &Function<Testnet3>,
+    user_inputs: &[UserInputValueType],
+    constraint_system: ConstraintSystemRef<ConstraintF>,
+    universal_srs: &UniversalSRS,
+    function_variables: &mut SimpleFunctionVariables,
+) -> Result<FunctionKeys> {
+    synthesize_function(
         program,
+        imports,
         function,
+        user_inputs,
+        &constraint_system,
         function_variables,
+    )?;
+    generate_function_keys(universal_srs, constraint_system)
+}
+
+/// Builds the verifying key of a function for the given constant inputs, in
+/// the order they are declared. Constants are hardcoded into the circuit
+/// instead of being public inputs, so a proof only verifies against the
+/// verifying key built with the same constants the prover used.
+pub fn build_verifying_key_for_constants(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function_name: &Identifier,
+    constants: &[UserInputValueType],
+    universal_srs: &UniversalSRS,
+) -> Result<VerifyingKey> {
+    let function = program.get_function(function_name)?;
+    let mut inputs = helpers::default_user_inputs(program, imports, function_name)?;
+    let mut constants_iter = constants.iter();
+    for (function_input, input) in function.inputs().iter().zip(inputs.iter_mut()) {
+        if let ValueType::Constant(_) = function_input.value_type() {
+            *input = constants_iter
+                .next()
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Missing constant for input {} of function {function_name}",
+                        function_input.register()
+                    )
+                })?
+                .clone();
+        }
+    }
+    ensure!(
+        constants_iter.next().is_none(),
+        "Too many constants were given for function {function_name}"
+    );
+
+    let constraint_system = ConstraintSystem::<ConstraintF>::new_ref();
+    let (_function_proving_key, function_verifying_key) = build_function_with_imports(
+        program,
+        imports,
+        &function,
+        &inputs,
         constraint_system.clone(),
+        universal_srs,
+        &mut helpers::function_variables(&function, constraint_system)?,
     )?;
-    simpleworks::marlin::generate_proving_and_verifying_keys(universal_srs, constraint_system)
+    Ok(function_verifying_key)
+}
+
+/// Verifies a proof of a function with constant inputs, rebuilding the
+/// verifying key for the supplied constants. The proof does not verify if
+/// the prover used different constants.
+pub fn verify_proof_with_constants(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function_name: &Identifier,
+    constants: &[UserInputValueType],
+    public_inputs: &[UserInputValueType],
+    proof: &MarlinProof,
+    universal_srs: &UniversalSRS,
+) -> Result<bool> {
+    let verifying_key = build_verifying_key_for_constants(
+        program,
+        imports,
+        function_name,
+        constants,
+        universal_srs,
+    )?;
+    verify_proof(verifying_key, public_inputs, proof)
 }
 
 /// Note: this function will always generate the same universal parameters because
@@ -184,8 +494,7 @@ pub fn build_function(
 /// these parameters will be something generated in a setup ceremony and thus it will not be possible
 /// to derive them deterministically like this.
 pub fn generate_universal_srs() -> Result<Box<UniversalSRS>> {
-    let rng = &mut simpleworks::marlin::generate_rand();
-    simpleworks::marlin::generate_universal_srs(100000, 25000, 300000, rng)
+    universal_srs::generate_universal_srs()
 }
 
 pub fn verify_proof(
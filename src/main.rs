@@ -1,9 +1,25 @@
-use anyhow::{anyhow, bail, Result};
-use ark_serialize::CanonicalSerialize;
-use clap::{Arg, ArgAction, Command, Parser, ValueHint};
-use lambdavm::jaleo::UserInputValueType;
+use anyhow::{anyhow, bail, ensure, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command, Parser, ValueHint};
+use lambdavm::jaleo::{
+    deserialize_proof, Account, Deployment, EncodedProof, EncryptedRecord, EncryptionScheme,
+    Identifier, PrivateKey, Record, Transition, UserInputValueType, ViewKey, PROOF_PREFIX,
+};
+use lambdavm::{
+    ceremony,
+    debugger::{Debugger, Step},
+    universal_srs::{self, LoadMode, UniversalSRSBounds},
+    CircuitIOType, ProgramBuild,
+};
+use serde_json::{json, Value};
+use simpleworks::marlin::MarlinProof;
 use snarkvm::prelude::{Parser as AleoParser, Program, Testnet3};
-use std::path::PathBuf;
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Instant,
+};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -14,62 +30,686 @@ struct Args {
     function_name: String,
 }
 
+/// Format in which the commands print their results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Results of a command. In text mode they are printed right away as human
+/// readable lines, in JSON mode they are gathered and printed as a single
+/// object when the command finishes, so scripts can parse them.
+struct Output {
+    format: Format,
+    values: serde_json::Map<String, Value>,
+}
+
+impl Output {
+    fn new(format: Format) -> Self {
+        Self {
+            format,
+            values: serde_json::Map::new(),
+        }
+    }
+
+    /// Prints a progress message, which is omitted in JSON mode.
+    fn progress(&self, message: impl Display) {
+        if self.format == Format::Text {
+            println!("{message}");
+        }
+    }
+
+    /// Outputs a result, as the given text or under the given key.
+    fn result(&mut self, key: &str, text: impl Display, value: impl Into<Value>) {
+        match self.format {
+            Format::Text => println!("{text}"),
+            Format::Json => {
+                self.values.insert(key.to_owned(), value.into());
+            }
+        }
+    }
+}
+
 fn main() -> Result<()> {
-    let matches = Command::new("lambdavm")
+    let matches = cli().get_matches();
+    let format = match matches.get_one::<String>("format").map(String::as_str) {
+        Some("json") => Format::Json,
+        _ => Format::Text,
+    };
+
+    let mut output = Output::new(format);
+    let start = Instant::now();
+    let result = run(&matches, &mut output);
+    match format {
+        Format::Text => result,
+        Format::Json => {
+            let mut values = output.values;
+            values.insert("success".to_owned(), result.is_ok().into());
+            if let Err(e) = &result {
+                values.insert("error".to_owned(), e.to_string().into());
+            }
+            values.insert(
+                "elapsed_ms".to_owned(),
+                u64::try_from(start.elapsed().as_millis())?.into(),
+            );
+            println!("{}", serde_json::to_string_pretty(&Value::Object(values))?);
+            if result.is_err() {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn cli() -> Command {
+    Command::new("lambdavm")
+        // Output format of every command.
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .global(true)
+                .value_parser(["text", "json"])
+                .default_value("text"),
+        )
         .subcommand(
             Command::new("execute")
                 // Function to execute.
                 .arg(Arg::new("function").required(true))
                 // Path of the program.
                 .arg(Arg::new("from").required(true))
+                // Directory of a program build, to load the keys from instead
+                // of generating them.
+                .arg(
+                    Arg::new("build")
+                        .long("build")
+                        .value_hint(ValueHint::DirPath),
+                )
+                // Output the transitions of the execution instead of the
+                // registers and the proof, as JSON lines.
+                .arg(
+                    Arg::new("transitions")
+                        .long("transitions")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("build"),
+                )
+                // Private key used for the serial numbers of the input
+                // records, the one of the stored account is used if not
+                // given.
+                .arg(
+                    Arg::new("private_key")
+                        .long("private-key")
+                        .requires("transitions"),
+                )
+                // Scheme the output records are encrypted with, Poseidon
+                // being the one Aleo wallets can decrypt.
+                .arg(
+                    Arg::new("encryption")
+                        .long("encryption")
+                        .value_parser(["aes-gcm", "poseidon"])
+                        .default_value("aes-gcm")
+                        .requires("transitions"),
+                )
+                // JSON file with the inputs, either an array in the order of
+                // the function inputs or a map keyed by register name. It
+                // is read from stdin if the path is "-".
+                .arg(
+                    Arg::new("inputs_file")
+                        .long("inputs-file")
+                        .conflicts_with("inputs")
+                        .value_hint(ValueHint::FilePath),
+                )
+                .arg(import_arg())
                 // Note: If another argument is to be added, we need to limit the
                 // number of inputs with value_terminator.
                 // Inputs required for the function (if needed).
                 .arg(Arg::new("inputs").num_args(1..).action(ArgAction::Append)),
         )
-        .subcommand(Command::new("generate_parameters"))
-        .get_matches();
+        .subcommand(
+            Command::new("check")
+                // Path of the program.
+                .arg(Arg::new("from").required(true)),
+        )
+        .subcommand(
+            Command::new("debug")
+                // Path of the program.
+                .arg(Arg::new("from").required(true))
+                // Function to debug.
+                .arg(Arg::new("function").required(true))
+                // Indexes of the instructions to stop at before processing them.
+                .arg(
+                    Arg::new("break")
+                        .long("break")
+                        .value_parser(clap::value_parser!(usize))
+                        .action(ArgAction::Append),
+                )
+                .arg(import_arg())
+                // Inputs required for the function (if needed).
+                .arg(Arg::new("inputs").num_args(1..).action(ArgAction::Append)),
+        )
+        .subcommand(
+            Command::new("build")
+                // Path of the program.
+                .arg(Arg::new("from").required(true))
+                // Directory where the program build is written.
+                .arg(
+                    Arg::new("out")
+                        .long("out")
+                        .default_value(".")
+                        .value_hint(ValueHint::DirPath),
+                )
+                // Write the build in the binary format instead of JSON.
+                .arg(Arg::new("binary").long("binary").action(ArgAction::SetTrue))
+                .arg(import_arg()),
+        )
+        .subcommand(
+            Command::new("deploy")
+                // Path of the program.
+                .arg(Arg::new("from").required_unless_present("check"))
+                // Path where the deployment is written.
+                .arg(
+                    Arg::new("out")
+                        .short('o')
+                        .long("out")
+                        .default_value("deployment.json")
+                        .value_hint(ValueHint::FilePath),
+                )
+                // Path of an existing deployment to check instead.
+                .arg(
+                    Arg::new("check")
+                        .long("check")
+                        .conflicts_with("from")
+                        .value_hint(ValueHint::FilePath),
+                )
+                // Write the deployment in the binary format instead of JSON.
+                .arg(
+                    Arg::new("binary")
+                        .long("binary")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("check"),
+                )
+                .arg(import_arg()),
+        )
+        .subcommand(
+            Command::new("account")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("new")
+                        // Seed to deterministically generate the account.
+                        .arg(
+                            Arg::new("seed")
+                                .long("seed")
+                                .value_parser(clap::value_parser!(u64)),
+                        )
+                        .arg(force_arg()),
+                )
+                .subcommand(
+                    // Private key of the account to import.
+                    Command::new("import")
+                        .arg(Arg::new("private_key").required(true))
+                        .arg(force_arg()),
+                )
+                .subcommand(Command::new("show")),
+        )
+        .subcommand(
+            Command::new("record")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("decrypt")
+                        // Ciphertext of the record.
+                        .arg(Arg::new("ciphertext").required(true))
+                        .args(record_key_args()),
+                )
+                .subcommand(
+                    Command::new("scan")
+                        // Path of the transitions, one per line as `execute --transitions`
+                        // prints them.
+                        .arg(Arg::new("transitions").required(true))
+                        .args(record_key_args()),
+                ),
+        )
+        .subcommand(
+            Command::new("verify")
+                // Path of the program.
+                .arg(Arg::new("program").long("program").required(true))
+                // Function whose proof is verified.
+                .arg(Arg::new("function").long("function").required(true))
+                // `proof1...` encoded proof or path of a file containing it.
+                .arg(Arg::new("proof").long("proof").required(true))
+                // Public inputs of the function, in order.
+                .arg(
+                    Arg::new("inputs")
+                        .long("inputs")
+                        .num_args(1..)
+                        .action(ArgAction::Append),
+                )
+                // Constant inputs of the function, in order.
+                .arg(
+                    Arg::new("constants")
+                        .long("constants")
+                        .num_args(1..)
+                        .action(ArgAction::Append),
+                )
+                .arg(import_arg()),
+        )
+        .subcommand(
+            Command::new("verify-transition")
+                // Path of the transition, or of several one per line.
+                .arg(Arg::new("transition").required(true))
+                // Path of the deployment of the transition's program.
+                .arg(Arg::new("deployment").long("deployment").required(true))
+                .arg(import_arg()),
+        )
+        .subcommand(
+            Command::new("srs")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("contribute")
+                        // Parameters to contribute to.
+                        .arg(Arg::new("input").required(true))
+                        // Where to store the parameters after the contribution.
+                        .arg(Arg::new("output").required(true))
+                        // Transcript the contribution is appended to.
+                        .arg(Arg::new("transcript").long("transcript").required(true)),
+                )
+                .subcommand(
+                    Command::new("verify-contribution")
+                        // Transcript with the contributions.
+                        .arg(Arg::new("transcript").long("transcript").required(true))
+                        // Parameters files, each one contributed to the one before.
+                        .arg(
+                            Arg::new("parameters")
+                                .required(true)
+                                .num_args(2..)
+                                .action(ArgAction::Append),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("generate_parameters")
+                // Where to store the parameters, defaults to the configured path.
+                .arg(Arg::new("out").long("out").short('o'))
+                // Size of the largest circuit the parameters are for.
+                .arg(
+                    Arg::new("num_constraints")
+                        .long("num-constraints")
+                        .value_parser(clap::value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("num_variables")
+                        .long("num-variables")
+                        .value_parser(clap::value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("num_non_zero")
+                        .long("num-non-zero")
+                        .value_parser(clap::value_parser!(usize)),
+                )
+                // Check existing parameters instead of generating new ones.
+                .arg(
+                    Arg::new("check")
+                        .long("check")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with_all(["num_constraints", "num_variables", "num_non_zero"]),
+                ),
+        )
+}
 
+fn run(matches: &ArgMatches, output: &mut Output) -> Result<()> {
     match matches.subcommand_name() {
         Some("execute") => {
-            let (inputs, function_name, program_string) = match matches.subcommand() {
-                Some(("execute", execute_cmd)) => {
-                    let function_name: String = execute_cmd
-                        .get_one::<String>("function")
-                        .ok_or_else(|| anyhow!("Error parsing function name parameter"))?
-                        .to_string();
-                    let program_string: String = execute_cmd
+            let execute_cmd = match matches.subcommand() {
+                Some(("execute", execute_cmd)) => execute_cmd,
+                _ => bail!("Unsupported command."),
+            };
+            let function_name = execute_cmd
+                .get_one::<String>("function")
+                .ok_or_else(|| anyhow!("Error parsing function name parameter"))?;
+            let program_string = execute_cmd
+                .get_one::<String>("from")
+                .ok_or_else(|| anyhow!("Error parsing program_string parameter"))?;
+            let build_dir = execute_cmd.get_one::<String>("build").map(PathBuf::from);
+            let transitions_private_key = if execute_cmd.get_flag("transitions") {
+                ensure!(
+                    output.format == Format::Text,
+                    "--transitions already prints JSON lines, it cannot be combined with --format json"
+                );
+                match execute_cmd.get_one::<String>("private_key") {
+                    Some(private_key) => Some(PrivateKey::from_str(private_key)?),
+                    None => Some(Account::load_from_file()?.private_key),
+                }
+            } else {
+                None
+            };
+
+            let encryption_scheme = match execute_cmd
+                .get_one::<String>("encryption")
+                .map(String::as_str)
+            {
+                Some("poseidon") => EncryptionScheme::Poseidon,
+                _ => EncryptionScheme::AesGcm,
+            };
+
+            let program = read_program(program_string)?;
+            let imports = read_imports(execute_cmd)?;
+            let function = program.get_function(&Identifier::try_from(function_name.as_str())?)?;
+            let user_inputs = match execute_cmd.get_one::<String>("inputs_file") {
+                Some(inputs_file) => read_inputs_file(inputs_file, &function)?,
+                None => parse_user_inputs(execute_cmd.get_many::<String>("inputs"))?,
+            };
+            lambdavm::helpers::check_user_inputs(&program, &imports, &function, &user_inputs)?;
+
+            match transitions_private_key {
+                Some(private_key) => execute_transitions(
+                    &program,
+                    &imports,
+                    function_name,
+                    &user_inputs,
+                    &private_key,
+                    encryption_scheme,
+                    output,
+                ),
+                None => execute(
+                    &program,
+                    &imports,
+                    function_name,
+                    &user_inputs,
+                    build_dir.as_deref(),
+                    output,
+                ),
+            }
+        }
+        Some("check") => {
+            let program_string = match matches.subcommand() {
+                Some(("check", check_cmd)) => check_cmd
+                    .get_one::<String>("from")
+                    .ok_or_else(|| anyhow!("Error parsing program_string parameter"))?,
+                _ => bail!("Unsupported command."),
+            };
+
+            check(program_string, output)
+        }
+        Some("debug") => {
+            let debug_cmd = match matches.subcommand() {
+                Some(("debug", debug_cmd)) => debug_cmd,
+                _ => bail!("Unsupported command."),
+            };
+            let program_string = debug_cmd
+                .get_one::<String>("from")
+                .ok_or_else(|| anyhow!("Error parsing program_string parameter"))?;
+            let function_name = debug_cmd
+                .get_one::<String>("function")
+                .ok_or_else(|| anyhow!("Error parsing function name parameter"))?;
+            let breakpoints = debug_cmd
+                .get_many::<usize>("break")
+                .into_iter()
+                .flatten()
+                .copied()
+                .collect();
+            let user_inputs = parse_user_inputs(debug_cmd.get_many::<String>("inputs"))?;
+
+            let program = read_program(program_string)?;
+            let imports = read_imports(debug_cmd)?;
+            debug(
+                &program,
+                &imports,
+                function_name,
+                &user_inputs,
+                breakpoints,
+                output,
+            )
+        }
+        Some("build") => {
+            let (program_string, out_dir, binary, imports) = match matches.subcommand() {
+                Some(("build", build_cmd)) => {
+                    let program_string: String = build_cmd
                         .get_one::<String>("from")
                         .ok_or_else(|| anyhow!("Error parsing program_string parameter"))?
                         .to_string();
-                    #[allow(deprecated)]
-                    let inputs: Vec<String> = execute_cmd
-                        .get_occurrences("inputs")
-                        .ok_or_else(|| anyhow!("Error parsing input parameters"))?
-                        .map(Iterator::collect)
-                        .collect::<Vec<Vec<&String>>>()
-                        .get(0)
-                        .ok_or_else(|| anyhow!("Error parsing input parameters"))?
-                        .iter()
-                        .map(|v| (*v).to_string())
-                        .collect();
+                    let out_dir = build_cmd
+                        .get_one::<String>("out")
+                        .map(PathBuf::from)
+                        .ok_or_else(|| anyhow!("Error parsing out parameter"))?;
+                    (
+                        program_string,
+                        out_dir,
+                        build_cmd.get_flag("binary"),
+                        read_imports(build_cmd)?,
+                    )
+                }
+                _ => bail!("Unsupported command."),
+            };
+
+            build(&program_string, &imports, &out_dir, binary, output)
+        }
+        Some("deploy") => {
+            let deploy_cmd = match matches.subcommand() {
+                Some(("deploy", deploy_cmd)) => deploy_cmd,
+                _ => bail!("Unsupported command."),
+            };
 
-                    (inputs, function_name, program_string)
+            let imports = read_imports(deploy_cmd)?;
+            match deploy_cmd.get_one::<String>("check") {
+                Some(deployment_path) => check_deployment(deployment_path, &imports, output),
+                None => {
+                    let program_string = deploy_cmd
+                        .get_one::<String>("from")
+                        .ok_or_else(|| anyhow!("Error parsing program_string parameter"))?;
+                    let out_path = deploy_cmd
+                        .get_one::<String>("out")
+                        .ok_or_else(|| anyhow!("Error parsing out parameter"))?;
+                    deploy(
+                        program_string,
+                        &imports,
+                        out_path,
+                        deploy_cmd.get_flag("binary"),
+                        output,
+                    )
                 }
+            }
+        }
+        Some("account") => {
+            let account = match matches.subcommand() {
+                Some(("account", account_cmd)) => match account_cmd.subcommand() {
+                    Some(("new", new_cmd)) => {
+                        let account = Account::new(new_cmd.get_one::<u64>("seed").copied())?;
+                        let file_path = account.write_to_file(new_cmd.get_flag("force"))?;
+                        output.progress(format!("Stored account under {file_path:?}"));
+                        account
+                    }
+                    Some(("import", import_cmd)) => {
+                        let account = import_cmd
+                            .get_one::<String>("private_key")
+                            .ok_or_else(|| anyhow!("Error parsing private key parameter"))?
+                            .parse::<Account>()?;
+                        let file_path = account.write_to_file(import_cmd.get_flag("force"))?;
+                        output.progress(format!("Stored account under {file_path:?}"));
+                        account
+                    }
+                    Some(("show", _)) => Account::load_from_file()?,
+                    _ => bail!("Unsupported command."),
+                },
                 _ => bail!("Unsupported command."),
             };
 
-            let mut vec_user_inputs = Vec::<UserInputValueType>::new();
-            for input_value in inputs.iter().rev() {
-                let v = UserInputValueType::try_from(input_value.clone())?;
-                vec_user_inputs.push(v);
+            output.result("account", &account, serde_json::to_value(&account)?);
+            Ok(())
+        }
+        Some("record") => {
+            let record_cmd = match matches.subcommand() {
+                Some(("record", record_cmd)) => record_cmd,
+                _ => bail!("Unsupported command."),
+            };
+            match record_cmd.subcommand() {
+                Some(("decrypt", decrypt_cmd)) => {
+                    let ciphertext = decrypt_cmd
+                        .get_one::<String>("ciphertext")
+                        .ok_or_else(|| anyhow!("Error parsing ciphertext parameter"))?;
+                    let (view_key, private_key) = record_keys(decrypt_cmd)?;
+                    decrypt_record(ciphertext, &view_key, private_key.as_ref(), output)
+                }
+                Some(("scan", scan_cmd)) => {
+                    let transitions_path = scan_cmd
+                        .get_one::<String>("transitions")
+                        .ok_or_else(|| anyhow!("Error parsing transitions parameter"))?;
+                    let (view_key, private_key) = record_keys(scan_cmd)?;
+                    scan_records(transitions_path, &view_key, private_key.as_ref(), output)
+                }
+                _ => bail!("Unsupported command."),
             }
+        }
+        Some("verify") => {
+            let verify_cmd = match matches.subcommand() {
+                Some(("verify", verify_cmd)) => verify_cmd,
+                _ => bail!("Unsupported command."),
+            };
+            let program_string = verify_cmd
+                .get_one::<String>("program")
+                .ok_or_else(|| anyhow!("Error parsing program parameter"))?;
+            let function_name = verify_cmd
+                .get_one::<String>("function")
+                .ok_or_else(|| anyhow!("Error parsing function parameter"))?;
+            let proof = verify_cmd
+                .get_one::<String>("proof")
+                .ok_or_else(|| anyhow!("Error parsing proof parameter"))?;
+            let inputs = parse_user_inputs(verify_cmd.get_many::<String>("inputs"))?;
+            let constants = parse_user_inputs(verify_cmd.get_many::<String>("constants"))?;
+            let imports = read_imports(verify_cmd)?;
 
-            execute(&function_name, &program_string, &vec_user_inputs)
+            verify(
+                program_string,
+                &imports,
+                function_name,
+                proof,
+                &inputs,
+                &constants,
+                output,
+            )
+        }
+        Some("verify-transition") => {
+            let verify_cmd = match matches.subcommand() {
+                Some(("verify-transition", verify_cmd)) => verify_cmd,
+                _ => bail!("Unsupported command."),
+            };
+            let transition_path = verify_cmd
+                .get_one::<String>("transition")
+                .ok_or_else(|| anyhow!("Error parsing transition parameter"))?;
+            let deployment_path = verify_cmd
+                .get_one::<String>("deployment")
+                .ok_or_else(|| anyhow!("Error parsing deployment parameter"))?;
+
+            let imports = read_imports(verify_cmd)?;
+
+            verify_transition(transition_path, deployment_path, &imports, output)
+        }
+        Some("srs") => {
+            let srs_cmd = match matches.subcommand() {
+                Some(("srs", srs_cmd)) => srs_cmd,
+                _ => bail!("Unsupported command."),
+            };
+            match srs_cmd.subcommand() {
+                Some(("contribute", contribute_cmd)) => {
+                    let input = contribute_cmd
+                        .get_one::<String>("input")
+                        .ok_or_else(|| anyhow!("Error parsing input parameter"))?;
+                    let output_path = contribute_cmd
+                        .get_one::<String>("output")
+                        .ok_or_else(|| anyhow!("Error parsing output parameter"))?;
+                    let transcript = contribute_cmd
+                        .get_one::<String>("transcript")
+                        .ok_or_else(|| anyhow!("Error parsing transcript parameter"))?;
+                    output.progress(format!("Contributing to universal parameters {input}..."));
+                    ceremony::contribute_to_file(
+                        Path::new(input),
+                        Path::new(output_path),
+                        Path::new(transcript),
+                    )?;
+                    output.result(
+                        "universal_srs",
+                        format!(
+                            "Stored universal parameters under {output_path:?} and appended the contribution to {transcript:?}"
+                        ),
+                        output_path.as_str(),
+                    );
+                    Ok(())
+                }
+                Some(("verify-contribution", verify_cmd)) => {
+                    let transcript = verify_cmd
+                        .get_one::<String>("transcript")
+                        .ok_or_else(|| anyhow!("Error parsing transcript parameter"))?;
+                    let parameters: Vec<&Path> = verify_cmd
+                        .get_many::<String>("parameters")
+                        .ok_or_else(|| anyhow!("Error parsing parameters parameter"))?
+                        .map(Path::new)
+                        .collect();
+                    output.progress("Verifying contributions...");
+                    let positions =
+                        ceremony::verify_contribution_files(Path::new(transcript), &parameters)?;
+                    output.result(
+                        "contributions",
+                        format!(
+                            "Contributions {} of the transcript are valid",
+                            positions
+                                .iter()
+                                .map(ToString::to_string)
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                        positions,
+                    );
+                    Ok(())
+                }
+                _ => bail!("Unsupported command."),
+            }
         }
         Some("generate_parameters") => {
-            let file_dir = lambdavm::universal_srs::generate_universal_srs_and_write_to_file()?;
-            println!("Stored universal parameters under {file_dir:?}");
+            let parameters_cmd = match matches.subcommand() {
+                Some(("generate_parameters", parameters_cmd)) => parameters_cmd,
+                _ => bail!("Unsupported command."),
+            };
+            let file_dir = match parameters_cmd.get_one::<String>("out") {
+                Some(out) => PathBuf::from(out),
+                None => universal_srs::get_universal_srs_dir_and_filepath()?.1,
+            };
+
+            if parameters_cmd.get_flag("check") {
+                output.progress(format!("Checking universal parameters {file_dir:?}"));
+                universal_srs::load_universal_srs_from_path(&file_dir, LoadMode::Checked)?;
+                let header = universal_srs::read_universal_srs_header(&file_dir)?;
+                output.result(
+                    "universal_srs",
+                    format!(
+                        "Universal parameters {file_dir:?} are valid (version {}, generated for {})",
+                        header.version, header.bounds
+                    ),
+                    json!({
+                        "path": file_dir.display().to_string(),
+                        "version": header.version,
+                        "bounds": header.bounds,
+                    }),
+                );
+                return Ok(());
+            }
+
+            let default_bounds = UniversalSRSBounds::DEFAULT;
+            let bound = |name: &str, default: usize| {
+                parameters_cmd
+                    .get_one::<usize>(name)
+                    .copied()
+                    .unwrap_or(default)
+            };
+            let bounds = UniversalSRSBounds {
+                num_constraints: bound("num_constraints", default_bounds.num_constraints),
+                num_variables: bound("num_variables", default_bounds.num_variables),
+                num_non_zero: bound("num_non_zero", default_bounds.num_non_zero),
+            };
+            output.progress(format!("Generating universal parameters for {bounds}"));
+            universal_srs::generate_universal_srs_and_write_to_path(&file_dir, &bounds)?;
+            output.result(
+                "universal_srs",
+                format!("Stored universal parameters under {file_dir:?}"),
+                file_dir.display().to_string(),
+            );
 
             Ok(())
         }
@@ -78,35 +718,643 @@ fn main() -> Result<()> {
     }
 }
 
-fn execute(
+/// Flag to overwrite the stored account, whose private key is lost otherwise.
+fn force_arg() -> Arg {
+    Arg::new("force").long("force").action(ArgAction::SetTrue)
+}
+
+/// Arguments of the record commands. Records are decrypted with the view key,
+/// but the private key is needed to compute their serial numbers.
+fn record_key_args() -> [Arg; 2] {
+    [
+        Arg::new("view_key")
+            .long("view-key")
+            .required_unless_present("private_key"),
+        Arg::new("private_key")
+            .long("private-key")
+            .conflicts_with("view_key"),
+    ]
+}
+
+fn record_keys(cmd: &ArgMatches) -> Result<(ViewKey, Option<PrivateKey>)> {
+    match (
+        cmd.get_one::<String>("view_key"),
+        cmd.get_one::<String>("private_key"),
+    ) {
+        (_, Some(private_key)) => {
+            let private_key = PrivateKey::from_str(private_key)?;
+            Ok((ViewKey::try_from(private_key)?, Some(private_key)))
+        }
+        (Some(view_key), None) => Ok((ViewKey::from_str(view_key)?, None)),
+        (None, None) => bail!("Either a view key or a private key is needed"),
+    }
+}
+
+/// Returns a record as text and as JSON, along with its commitment and serial
+/// number (if the private key is known).
+fn record_output(
+    commitment: &str,
+    record: &Record,
+    private_key: Option<&PrivateKey>,
+) -> Result<(String, Value)> {
+    let serial_number = private_key
+        .map(|private_key| record.serial_number(private_key))
+        .transpose()?;
+    let text = format!(
+        "Record: {}\nCommitment: {commitment}\nSerial number: {}",
+        record.to_plaintext()?,
+        serial_number
+            .clone()
+            .unwrap_or_else(|| "unknown (requires the private key)".to_owned())
+    );
+    let value = json!({
+        "record": record,
+        "commitment": commitment,
+        "serial_number": serial_number,
+    });
+    Ok((text, value))
+}
+
+fn decrypt_record(
+    ciphertext: &str,
+    view_key: &ViewKey,
+    private_key: Option<&PrivateKey>,
+    output: &mut Output,
+) -> Result<()> {
+    let record = EncryptedRecord::decrypt_from_ciphertext(view_key, ciphertext)
+        .map_err(|e| anyhow!("Error decrypting record: {e}"))?;
+    let (text, value) = record_output(&record.commitment()?, &record, private_key)?;
+    output.result("record", text, value);
+    Ok(())
+}
+
+fn scan_records(
+    transitions_path: &str,
+    view_key: &ViewKey,
+    private_key: Option<&PrivateKey>,
+    output: &mut Output,
+) -> Result<()> {
+    let transitions = Transition::read_all_from_file(Path::new(transitions_path))?;
+
+    let mut records_text = Vec::new();
+    let mut records = Vec::new();
+    for transition in transitions {
+        for (commitment, record) in transition.owned_records(view_key) {
+            let (text, value) = record_output(&commitment, &record, private_key)?;
+            records_text.push(text);
+            records.push(value);
+        }
+    }
+    records_text.push(format!("Found {} records", records.len()));
+    output.result("records", records_text.join("\n"), records);
+
+    Ok(())
+}
+
+fn parse_user_inputs<'a>(
+    inputs: Option<impl Iterator<Item = &'a String>>,
+) -> Result<Vec<UserInputValueType>> {
+    inputs
+        .into_iter()
+        .flatten()
+        .map(|input| UserInputValueType::try_from(input.clone()))
+        .collect()
+}
+
+fn read_program(program_string: &str) -> Result<Program<Testnet3>> {
+    let program_str = std::fs::read_to_string(program_string)
+        .map_err(|e| anyhow!("Error reading program {program_string}: {e}"))?;
+    let (_, program) = Program::<Testnet3>::parse(&program_str).map_err(|e| anyhow!("{}", e))?;
+    Ok(program)
+}
+
+/// Option to pass the programs imported by the program, which are needed for
+/// their external records. It can be given once per imported program.
+fn import_arg() -> Arg {
+    Arg::new("imports")
+        .long("import")
+        .value_hint(ValueHint::FilePath)
+        .action(ArgAction::Append)
+}
+
+fn read_imports(cmd: &ArgMatches) -> Result<Vec<Program<Testnet3>>> {
+    cmd.get_many::<String>("imports")
+        .into_iter()
+        .flatten()
+        .map(|import| read_program(import))
+        .collect()
+}
+
+fn parse_user_input(input: &serde_json::Value) -> Result<UserInputValueType> {
+    match input {
+        serde_json::Value::String(input) => UserInputValueType::try_from(input.clone()),
+        // Records are given as JSON objects.
+        other => UserInputValueType::try_from(other.to_string()),
+    }
+}
+
+/// Reads the inputs of a function from a JSON file (or stdin if the path is
+/// "-"). The inputs are either an array in the order of the function inputs
+/// or a map keyed by register name.
+fn read_inputs_file(
+    inputs_file: &str,
+    function: &snarkvm::prelude::Function<Testnet3>,
+) -> Result<Vec<UserInputValueType>> {
+    let serialized_inputs = if inputs_file == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(inputs_file)
+            .map_err(|e| anyhow!("Error reading inputs file {inputs_file}: {e}"))?
+    };
+    let inputs: serde_json::Value = serde_json::from_str(&serialized_inputs)
+        .map_err(|e| anyhow!("Error parsing inputs file {inputs_file}: {e}"))?;
+
+    match inputs {
+        serde_json::Value::Array(inputs) => inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| {
+                parse_user_input(input).map_err(|e| anyhow!("Invalid input at index {index}: {e}"))
+            })
+            .collect(),
+        serde_json::Value::Object(mut inputs) => {
+            let mut user_inputs = Vec::with_capacity(function.inputs().len());
+            for function_input in function.inputs() {
+                let register = function_input.register().to_string();
+                let input = inputs.remove(&register).ok_or_else(|| {
+                    anyhow!("Missing input {register} of function {}", function.name())
+                })?;
+                user_inputs.push(
+                    parse_user_input(&input)
+                        .map_err(|e| anyhow!("Invalid input {register}: {e}"))?,
+                );
+            }
+            if let Some(register) = inputs.keys().next() {
+                bail!("Function {} has no input {register}", function.name());
+            }
+            Ok(user_inputs)
+        }
+        _ => bail!("Inputs file {inputs_file} must contain an array or a map of inputs"),
+    }
+}
+
+/// Reads a `proof1...` encoded proof, either given directly or stored in a
+/// file. Hex encoded proofs are still accepted.
+fn read_proof(proof: &str) -> Result<MarlinProof> {
+    let encoded_proof = if Path::new(proof).is_file() {
+        std::fs::read_to_string(proof)?
+    } else {
+        proof.to_owned()
+    };
+    let encoded_proof = encoded_proof.trim();
+    if encoded_proof.starts_with(PROOF_PREFIX) {
+        return Ok(EncodedProof::from_str(encoded_proof)?.0);
+    }
+    let encoded_proof = encoded_proof.strip_prefix("0x").unwrap_or(encoded_proof);
+    let bytes_proof =
+        hex::decode(encoded_proof).map_err(|e| anyhow!("Error decoding proof: {e}"))?;
+    deserialize_proof(bytes_proof)
+}
+
+fn verify(
+    program_string: &str,
+    imports: &[Program<Testnet3>],
     function_name: &str,
+    proof: &str,
+    public_inputs: &[UserInputValueType],
+    constants: &[UserInputValueType],
+    output: &mut Output,
+) -> Result<()> {
+    output.progress(format!("Verifying proof of function {function_name}..."));
+
+    let program = read_program(program_string)?;
+    let proof = read_proof(proof)?;
+
+    let universal_srs = lambdavm::universal_srs::load_universal_srs_from_file()?;
+    let proof_is_valid = lambdavm::verify_proof_with_constants(
+        &program,
+        imports,
+        &Identifier::try_from(function_name)?,
+        constants,
+        public_inputs,
+        &proof,
+        &universal_srs,
+    )?;
+    ensure!(proof_is_valid, "Proof verification failed");
+
+    output.result("verified", "Proof verified", true);
+    Ok(())
+}
+
+fn read_deployment(deployment_path: &str) -> Result<Deployment> {
+    Deployment::read_from_file(Path::new(deployment_path))
+}
+
+fn deploy(
     program_string: &str,
+    imports: &[Program<Testnet3>],
+    out_path: &str,
+    binary: bool,
+    output: &mut Output,
+) -> Result<()> {
+    output.progress(format!("Deploying program {program_string}..."));
+
+    let program_str = std::fs::read_to_string(program_string)
+        .map_err(|e| anyhow!("Error reading program {program_string}: {e}"))?;
+    let deployment = lambdavm::jaleo::generate_deployment_with_imports(&program_str, imports)?;
+
+    if binary {
+        deployment.write_to_binary_file(Path::new(out_path))?;
+    } else {
+        std::fs::write(out_path, serde_json::to_string_pretty(&deployment)?)?;
+    }
+    output.result(
+        "deployment",
+        format!("Stored deployment under {out_path:?}"),
+        out_path,
+    );
+
+    Ok(())
+}
+
+fn check_deployment(
+    deployment_path: &str,
+    imports: &[Program<Testnet3>],
+    output: &mut Output,
+) -> Result<()> {
+    output.progress(format!("Checking deployment {deployment_path}..."));
+
+    let deployment = read_deployment(deployment_path)?;
+    lambdavm::jaleo::check_deployment_with_imports(&deployment, imports)?;
+
+    output.result(
+        "valid",
+        format!("Deployment of {} is valid", deployment.program.id()),
+        true,
+    );
+    Ok(())
+}
+
+fn verify_transition(
+    transition_path: &str,
+    deployment_path: &str,
+    imports: &[Program<Testnet3>],
+    output: &mut Output,
+) -> Result<()> {
+    let transitions = Transition::read_all_from_file(Path::new(transition_path))?;
+    ensure!(
+        !transitions.is_empty(),
+        "No transitions in {transition_path}"
+    );
+    let deployment = read_deployment(deployment_path)?;
+
+    for transition in &transitions {
+        output.progress(format!(
+            "Verifying transition of {}/{}...",
+            transition.program_id(),
+            transition.function_name()
+        ));
+
+        let transition_is_valid =
+            lambdavm::jaleo::verify_execution_with_imports(transition, &deployment, imports)?;
+        ensure!(transition_is_valid, "Transition verification failed");
+    }
+
+    let text = match transitions.len() {
+        1 => "Transition verified".to_owned(),
+        count => format!("{count} transitions verified"),
+    };
+    output.result("verified", text, true);
+    Ok(())
+}
+
+fn check(program_string: &str, output: &mut Output) -> Result<()> {
+    let program = read_program(program_string)?;
+    let diagnostics = lambdavm::check_program(&program);
+
+    let mut text: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+    text.push(format!(
+        "Found {} unsupported features in {}",
+        diagnostics.len(),
+        program.id()
+    ));
+    output.result(
+        "diagnostics",
+        text.join("\n"),
+        serde_json::to_value(&diagnostics)?,
+    );
+    ensure!(
+        diagnostics.is_empty(),
+        "Program {} uses unsupported features",
+        program.id()
+    );
+    Ok(())
+}
+
+fn register_value(value: Option<&CircuitIOType>) -> String {
+    match value.map(CircuitIOType::value) {
+        Some(Ok(value)) => value,
+        Some(Err(e)) => format!("<error: {e}>"),
+        None => "<unassigned>".to_owned(),
+    }
+}
+
+fn step_output(step: &Step, debugger: &Debugger<'_>) -> (String, Value) {
+    let destination_value = step.destination.as_ref().map(|destination| {
+        register_value(
+            debugger
+                .registers()
+                .get(destination)
+                .and_then(Option::as_ref),
+        )
+    });
+    let mut text = format!(
+        "[{}] {} (+{} constraints, {})",
+        step.index,
+        step.instruction,
+        step.constraints,
+        if step.satisfied {
+            "satisfied"
+        } else {
+            "NOT satisfied"
+        }
+    );
+    if let (Some(destination), Some(value)) = (&step.destination, &destination_value) {
+        text.push_str(&format!("\n    {destination} = {value}"));
+    }
+    let value = json!({
+        "index": step.index,
+        "instruction": step.instruction,
+        "destination": step.destination,
+        "value": destination_value,
+        "constraints": step.constraints,
+        "satisfied": step.satisfied,
+    });
+    (text, value)
+}
+
+const DEBUG_HELP: &str = "Commands:
+    s, step             process the next instruction (also an empty line)
+    c, continue         process instructions until a breakpoint or the end
+    b, break <index>    stop before the instruction at the given index
+    r, registers        show the value of every register
+    l, list             show the instructions of the function
+    q, quit             stop debugging
+    h, help             show this message";
+
+/// Steps through the instructions of a function, showing what each of them
+/// does to the registers and the constraint system. In JSON mode it runs
+/// to the end and outputs every step instead.
+fn debug(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function_name: &str,
     user_inputs: &[UserInputValueType],
+    mut breakpoints: BTreeSet<usize>,
+    output: &mut Output,
 ) -> Result<()> {
-    println!("Executing function {function_name}...");
+    let mut debugger = Debugger::new(program, imports, function_name, user_inputs)?;
+    let list_instructions = |debugger: &Debugger<'_>| {
+        for (index, instruction) in debugger.instructions().iter().enumerate() {
+            let marker = if index == debugger.next_instruction() {
+                "=>"
+            } else {
+                "  "
+            };
+            println!("{marker} [{index}] {instruction}");
+        }
+    };
 
-    // TODO: We need to reverse to do things in the right order. Revisit this.
-    let mut inputs_copy = user_inputs.to_vec();
-    inputs_copy.reverse();
+    if output.format == Format::Json {
+        let mut steps = Vec::new();
+        while let Some(step) = debugger.step()? {
+            steps.push(step_output(&step, &debugger).1);
+        }
+        output.result("steps", "", steps);
+        output.result("satisfied", "", debugger.is_satisfied()?);
+        return Ok(());
+    }
 
-    let program_str = std::fs::read_to_string(program_string).unwrap();
+    println!(
+        "Debugging function {function_name}, inputs processed with {} constraints",
+        debugger.num_constraints()
+    );
+    list_instructions(&debugger);
+    println!("{DEBUG_HELP}");
 
-    let (_, program) = Program::<Testnet3>::parse(&program_str).map_err(|e| anyhow!("{}", e))?;
+    let stdin = std::io::stdin();
+    // Without breakpoints it stops before the first instruction, otherwise it
+    // runs until the first one.
+    let mut continuing = !breakpoints.is_empty();
+    loop {
+        if debugger.is_finished() {
+            println!(
+                "Finished with {} constraints, {}",
+                debugger.num_constraints(),
+                if debugger.is_satisfied()? {
+                    "satisfied"
+                } else {
+                    "NOT satisfied"
+                }
+            );
+            return Ok(());
+        }
+
+        if continuing && !breakpoints.contains(&debugger.next_instruction()) {
+            if let Some(step) = debugger.step()? {
+                println!("{}", step_output(&step, &debugger).0);
+            }
+            continue;
+        }
+        continuing = false;
 
-    let (_compiled_function_variables, proof) =
-        lambdavm::execute_function(&program, function_name, &inputs_copy)?;
+        print!("({}) > ", debugger.next_instruction());
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            // Nothing else to read, run until the end.
+            breakpoints.clear();
+            continuing = true;
+            continue;
+        }
 
-    for (register, value) in _compiled_function_variables {
-        println!(
-            "Output register {register} has value {}",
-            value.unwrap().value()?
-        );
+        let mut words = line.split_whitespace();
+        match words.next() {
+            None | Some("s" | "step") => {
+                if let Some(step) = debugger.step()? {
+                    println!("{}", step_output(&step, &debugger).0);
+                }
+            }
+            Some("c" | "continue") => {
+                if let Some(step) = debugger.step()? {
+                    println!("{}", step_output(&step, &debugger).0);
+                }
+                continuing = true;
+            }
+            Some("b" | "break") => match words.next().map(str::parse::<usize>) {
+                Some(Ok(index)) if index < debugger.instructions().len() => {
+                    breakpoints.insert(index);
+                    println!("Breakpoint set at instruction {index}");
+                }
+                _ => println!("Expected the index of an instruction"),
+            },
+            Some("r" | "registers") => {
+                for (register, value) in debugger.registers() {
+                    println!("{register} = {}", register_value(value.as_ref()));
+                }
+            }
+            Some("l" | "list") => list_instructions(&debugger),
+            Some("q" | "quit") => return Ok(()),
+            Some("h" | "help") => println!("{DEBUG_HELP}"),
+            Some(other) => println!("Unknown command {other}, type h for help"),
+        }
     }
+}
 
-    let mut bytes_proof = Vec::new();
-    match proof.serialize(&mut bytes_proof) {
-        Ok(_) => println!("Proof of execution: \n0x{}", hex::encode(bytes_proof)),
-        Err(_) => println!("⚠️ Error serializing proof"),
+/// Path of the build of the given program inside a build directory.
+fn program_build_path(build_dir: &Path, program: &Program<Testnet3>, binary: bool) -> PathBuf {
+    let extension = if binary { "bin" } else { "json" };
+    build_dir.join(format!("{}.build.{extension}", program.id().name()))
+}
+
+/// Path of an existing build of the given program, the binary one is
+/// preferred if there are both.
+fn existing_program_build_path(build_dir: &Path, program: &Program<Testnet3>) -> PathBuf {
+    let binary_path = program_build_path(build_dir, program, true);
+    if binary_path.exists() {
+        binary_path
+    } else {
+        program_build_path(build_dir, program, false)
+    }
+}
+
+fn build(
+    program_string: &str,
+    imports: &[Program<Testnet3>],
+    out_dir: &Path,
+    binary: bool,
+    output: &mut Output,
+) -> Result<()> {
+    output.progress(format!("Building program {program_string}..."));
+
+    let program_str = std::fs::read_to_string(program_string)
+        .map_err(|e| anyhow!("Error reading program {program_string}: {e}"))?;
+
+    let (program, program_build) = lambdavm::build_program_with_imports(&program_str, imports)?;
+
+    std::fs::create_dir_all(out_dir)?;
+    let build_path = program_build_path(out_dir, &program, binary);
+    if binary {
+        program_build.write_to_binary_file(&build_path)?;
+    } else {
+        program_build.write_to_file(&build_path)?;
+    }
+    output.result(
+        "build",
+        format!("Stored program build under {build_path:?}"),
+        build_path.display().to_string(),
+    );
+
+    Ok(())
+}
+
+/// Executes a function through the whole transition pipeline (encrypted
+/// output records, serial numbers, etc.) and prints the transitions as JSON
+/// lines, the format `record scan` and `verify-transition` read.
+/// The transitions are not signed, the private key is only used for the
+/// serial numbers of the input records.
+fn execute_transitions(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function_name: &str,
+    user_inputs: &[UserInputValueType],
+    private_key: &PrivateKey,
+    encryption_scheme: EncryptionScheme,
+    output: &mut Output,
+) -> Result<()> {
+    // Only the transitions are printed to stdout so they can be piped.
+    eprintln!("Executing function {function_name}...");
+
+    let transitions = lambdavm::jaleo::execution_with_imports(
+        program,
+        imports,
+        &Identifier::try_from(function_name)?,
+        user_inputs,
+        private_key,
+        encryption_scheme,
+    )?;
+
+    output.result(
+        "transitions",
+        Transition::to_json_lines(&transitions)?,
+        serde_json::to_value(&transitions)?,
+    );
+    Ok(())
+}
+
+fn execute(
+    program: &Program<Testnet3>,
+    imports: &[Program<Testnet3>],
+    function_name: &str,
+    user_inputs: &[UserInputValueType],
+    build_dir: Option<&Path>,
+    output: &mut Output,
+) -> Result<()> {
+    output.progress(format!("Executing function {function_name}..."));
+
+    let execution_start = Instant::now();
+    let (compiled_function_variables, proof, constraint_counts) = match build_dir {
+        Some(build_dir) => {
+            let program_build =
+                ProgramBuild::read_from_file(&existing_program_build_path(build_dir, program))?;
+            let (proving_key, _verifying_key) = program_build.get(function_name)?;
+            lambdavm::execute_function_with_proving_key(
+                program,
+                imports,
+                function_name,
+                user_inputs,
+                proving_key.clone(),
+            )?
+        }
+        None => {
+            lambdavm::execute_function_with_imports(program, imports, function_name, user_inputs)?
+        }
+    };
+    let execution_time = execution_start.elapsed();
+
+    let mut registers_text = Vec::new();
+    let mut registers = serde_json::Map::new();
+    for (register, value) in compiled_function_variables {
+        let value = value
+            .ok_or_else(|| anyhow!("Register {register} was not assigned"))?
+            .value()?;
+        registers_text.push(format!("Output register {register} has value {value}"));
+        registers.insert(register, value.into());
     }
+    output.result("registers", registers_text.join("\n"), registers);
+
+    output.result(
+        "constraints",
+        format!(
+            "Constraints: {} ({} public and {} private variables, {} non zero entries)",
+            constraint_counts.constraints,
+            constraint_counts.instance_variables,
+            constraint_counts.witness_variables,
+            constraint_counts.non_zero
+        ),
+        serde_json::to_value(constraint_counts)?,
+    );
+    output.result(
+        "execution_ms",
+        format!("Execution time: {execution_time:?}"),
+        u64::try_from(execution_time.as_millis())?,
+    );
+
+    let encoded_proof = EncodedProof(proof).to_string();
+    output.result(
+        "proof",
+        format!("Proof of execution: \n{encoded_proof}"),
+        encoded_proof,
+    );
     Ok(())
 }
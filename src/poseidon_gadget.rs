@@ -0,0 +1,273 @@
+use anyhow::{anyhow, Result};
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{Boolean, FieldVar},
+    R1CSVar, ToBitsGadget,
+};
+use ark_sponge::{
+    constraints::CryptographicSpongeVar,
+    poseidon::{constraints::PoseidonSpongeVar, PoseidonParameters},
+};
+use once_cell::sync::Lazy;
+use simpleworks::gadgets::{ConstraintF, UInt8Gadget};
+
+/// Number of field elements absorbed per permutation, which is the rate
+/// ark-sponge's Poseidon sponge uses.
+const RATE: usize = 2;
+/// arkworks' default S-box and number of rounds for this rate. These are
+/// parameters over the BLS12-381 scalar field, so the hashes are not the
+/// ones snarkVM computes over the BLS12-377 one.
+const ALPHA: u64 = 17;
+const FULL_ROUNDS: u32 = 8;
+const PARTIAL_ROUNDS: u32 = 31;
+/// Bytes packed into each field element, which must fit below the modulus.
+const BYTES_PER_FIELD_ELEMENT: usize = 31;
+
+static POSEIDON_PARAMETERS: Lazy<Result<PoseidonParameters<ConstraintF>>> = Lazy::new(|| {
+    let (ark, mds) = find_poseidon_ark_and_mds(RATE, FULL_ROUNDS, PARTIAL_ROUNDS)?;
+    Ok(PoseidonParameters::new(
+        FULL_ROUNDS,
+        PARTIAL_ROUNDS,
+        ALPHA,
+        mds,
+        ark,
+    ))
+});
+
+/// Grain LFSR from the Poseidon paper, used to generate the round constants
+/// and the MDS matrix the same way arkworks' `find_poseidon_ark_and_mds`
+/// does.
+struct PoseidonGrainLFSR {
+    state: [bool; 80],
+    head: usize,
+}
+
+impl PoseidonGrainLFSR {
+    fn new(state_len: usize, full_rounds: u32, partial_rounds: u32) -> Result<Self> {
+        // The field is a prime field and the S-box is x^alpha, then come the
+        // field size, the state length and the number of rounds in 12, 12,
+        // 10 and 10 bits, and the remaining 30 bits are set.
+        let fields = [
+            (u64::try_from(ConstraintF::size_in_bits())?, 12),
+            (u64::try_from(state_len)?, 12),
+            (u64::from(full_rounds), 10),
+            (u64::from(partial_rounds), 10),
+        ];
+        let bits = [false, true, false, false, false, false]
+            .into_iter()
+            .chain(fields.into_iter().flat_map(|(value, bit_count)| {
+                (0..bit_count)
+                    .rev()
+                    .map(move |position| (value >> position) & 1 == 1)
+            }))
+            .chain([true; 30])
+            .collect::<Vec<bool>>();
+        let state = bits
+            .try_into()
+            .map_err(|_| anyhow!("Error initializing the Poseidon Grain LFSR"))?;
+
+        let mut lfsr = Self { state, head: 0 };
+        for _ in 0..160 {
+            lfsr.update()?;
+        }
+        Ok(lfsr)
+    }
+
+    fn bit(&self, offset: usize) -> Result<bool> {
+        self.state
+            .get((self.head + offset) % 80)
+            .copied()
+            .ok_or_else(|| anyhow!("Error reading the Poseidon Grain LFSR"))
+    }
+
+    fn update(&mut self) -> Result<bool> {
+        let new_bit = [62, 51, 38, 23, 13, 0]
+            .into_iter()
+            .try_fold(false, |bit, offset| {
+                Ok::<_, anyhow::Error>(bit ^ self.bit(offset)?)
+            })?;
+        let head = self
+            .state
+            .get_mut(self.head)
+            .ok_or_else(|| anyhow!("Error updating the Poseidon Grain LFSR"))?;
+        *head = new_bit;
+        self.head = (self.head + 1) % 80;
+        Ok(new_bit)
+    }
+
+    /// Bits are output in pairs, keeping the second one only when the first
+    /// one is set.
+    fn bits(&mut self, bit_count: usize) -> Result<Vec<bool>> {
+        (0..bit_count)
+            .map(|_| {
+                while !self.update()? {
+                    self.update()?;
+                }
+                self.update()
+            })
+            .collect()
+    }
+
+    /// Field elements from the bits, most significant bit first, rejecting the
+    /// ones that are not below the modulus.
+    fn field_elements_rejection_sampling(&mut self, count: usize) -> Result<Vec<ConstraintF>> {
+        (0..count)
+            .map(|_| -> Result<ConstraintF> {
+                loop {
+                    let bits = self.bits(ConstraintF::size_in_bits())?;
+                    let repr = <ConstraintF as PrimeField>::BigInt::from_bits_be(&bits);
+                    if let Some(element) = ConstraintF::from_repr(repr) {
+                        break Ok(element);
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Field elements from the bits, most significant bit first, reduced
+    /// modulo the field size.
+    fn field_elements_mod_p(&mut self, count: usize) -> Result<Vec<ConstraintF>> {
+        (0..count)
+            .map(|_| -> Result<ConstraintF> {
+                let mut bits = self.bits(ConstraintF::size_in_bits())?;
+                bits.reverse();
+                let bytes = bits
+                    .chunks(8)
+                    .map(|chunk| {
+                        chunk
+                            .iter()
+                            .rev()
+                            .fold(0_u8, |byte, bit| (byte << 1) | u8::from(*bit))
+                    })
+                    .collect::<Vec<u8>>();
+                Ok(ConstraintF::from_le_bytes_mod_order(&bytes))
+            })
+            .collect()
+    }
+}
+
+/// Round constants and a Cauchy MDS matrix generated with the Grain LFSR,
+/// without skipping any matrix, as arkworks does for its default parameters.
+fn find_poseidon_ark_and_mds(
+    rate: usize,
+    full_rounds: u32,
+    partial_rounds: u32,
+) -> Result<(Vec<Vec<ConstraintF>>, Vec<Vec<ConstraintF>>)> {
+    let width = rate + 1;
+    let mut lfsr = PoseidonGrainLFSR::new(width, full_rounds, partial_rounds)?;
+
+    let ark = (0..full_rounds + partial_rounds)
+        .map(|_| lfsr.field_elements_rejection_sampling(width))
+        .collect::<Result<Vec<_>>>()?;
+
+    let xs = lfsr.field_elements_mod_p(width)?;
+    let ys = lfsr.field_elements_mod_p(width)?;
+    let mds = xs
+        .iter()
+        .map(|x| {
+            ys.iter()
+                .map(|y| {
+                    (*x + y)
+                        .inverse()
+                        .ok_or_else(|| anyhow!("Error building the Poseidon MDS matrix"))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((ark, mds))
+}
+
+/// Poseidon sponge over the given field elements, computed in the circuit.
+/// The number of inputs is absorbed first, so inputs of different lengths do
+/// not collide.
+pub(crate) fn poseidon_hash(inputs: &[FpVar<ConstraintF>]) -> Result<FpVar<ConstraintF>> {
+    let parameters = POSEIDON_PARAMETERS
+        .as_ref()
+        .map_err(|error| anyhow!("Error building the Poseidon parameters: {error}"))?;
+    let mut sponge = PoseidonSpongeVar::new(inputs.cs(), parameters);
+
+    sponge.absorb(&FpVar::constant(ConstraintF::from(u64::try_from(
+        inputs.len(),
+    )?)))?;
+    sponge.absorb(&inputs.to_vec())?;
+
+    sponge
+        .squeeze_field_elements(1)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("Error squeezing the Poseidon sponge"))
+}
+
+/// Poseidon hash of some bytes, packed little endian into field elements.
+pub(crate) fn poseidon_hash_bytes(bytes: &[UInt8Gadget]) -> Result<FpVar<ConstraintF>> {
+    let field_elements = bytes
+        .chunks(BYTES_PER_FIELD_ELEMENT)
+        .map(|chunk| -> Result<FpVar<ConstraintF>> {
+            let bits = chunk
+                .iter()
+                .map(|byte| byte.to_bits_le())
+                .collect::<Result<Vec<_>, _>>()?
+                .concat();
+            Ok(Boolean::le_bits_to_fp_var(&bits)?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    poseidon_hash(&field_elements)
+}
+
+#[cfg(test)]
+mod poseidon_gadget_tests {
+    use super::{poseidon_hash, poseidon_hash_bytes, POSEIDON_PARAMETERS};
+    use ark_r1cs_std::{
+        fields::fp::FpVar,
+        prelude::{AllocVar, FieldVar},
+        R1CSVar,
+    };
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_sponge::{poseidon::PoseidonSponge, CryptographicSponge};
+    use simpleworks::gadgets::{ConstraintF, UInt8Gadget};
+
+    #[test]
+    fn test_poseidon_hash_is_computed_in_the_circuit() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let bytes = Vec::<UInt8Gadget>::new_witness(cs.clone(), || Ok(b"hello".to_vec())).unwrap();
+        let constraints_before = cs.num_constraints();
+
+        let hash = poseidon_hash_bytes(&bytes).unwrap();
+        let constant_hash = poseidon_hash_bytes(&UInt8Gadget::constant_vec(b"hello")).unwrap();
+
+        assert!(cs.num_constraints() > constraints_before);
+        assert!(!hash.is_constant());
+        assert!(constant_hash.is_constant());
+        assert_eq!(hash.value().unwrap(), constant_hash.value().unwrap());
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_poseidon_hash_matches_the_native_sponge() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let inputs = vec![ConstraintF::from(1_u64), ConstraintF::from(2_u64)];
+        let input_vars = Vec::<FpVar<ConstraintF>>::new_witness(cs, || Ok(inputs.clone())).unwrap();
+
+        let hash = poseidon_hash(&input_vars).unwrap();
+
+        let mut sponge = PoseidonSponge::new(POSEIDON_PARAMETERS.as_ref().unwrap());
+        sponge.absorb(&ConstraintF::from(2_u64));
+        sponge.absorb(&inputs);
+        let expected_hash = sponge.squeeze_field_elements::<ConstraintF>(1);
+
+        assert_eq!(vec![hash.value().unwrap()], expected_hash);
+    }
+
+    #[test]
+    fn test_poseidon_hash_depends_on_the_length() {
+        let zero = FpVar::<ConstraintF>::zero();
+        let one_zero = poseidon_hash(&[zero.clone()]).unwrap();
+        let two_zeros = poseidon_hash(&[zero.clone(), zero]).unwrap();
+        let empty = poseidon_hash(&[]).unwrap();
+
+        assert_ne!(one_zero.value().unwrap(), two_zeros.value().unwrap());
+        assert_ne!(one_zero.value().unwrap(), empty.value().unwrap());
+    }
+}
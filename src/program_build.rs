@@ -1,4 +1,8 @@
-use crate::{jaleo::Identifier, FunctionKeys};
+use crate::{
+    binary::{self, Kind},
+    jaleo::Identifier,
+    FunctionKeys,
+};
 use anyhow::{anyhow, Result};
 use indexmap::IndexMap;
 use serde::{
@@ -10,7 +14,13 @@ use simpleworks::marlin::serialization::{
     deserialize_proving_key, deserialize_verifying_key, serialize_proving_key,
     serialize_verifying_key,
 };
-use std::{fmt::Debug, str::FromStr};
+use std::{
+    fmt::Debug,
+    fs,
+    io::{Read, Write},
+    path::Path,
+    str::FromStr,
+};
 
 pub struct ProgramBuild {
     pub map: IndexMap<Identifier, FunctionKeys>,
@@ -23,6 +33,57 @@ impl ProgramBuild {
             .get(&identifier)
             .ok_or_else(|| anyhow!("Error getting function from program build"))
     }
+
+    /// Writes the program build as JSON to the given path, so the keys can
+    /// be loaded later on instead of being generated again.
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        let serialized_build = serde_json::to_string(self)?;
+        fs::write(path, serialized_build)?;
+        Ok(())
+    }
+
+    /// Reads a program build previously written with `write_to_file` or
+    /// `write_to_binary_file`.
+    pub fn read_from_file(path: &Path) -> Result<Self> {
+        if binary::is_binary_file(path)? {
+            return Self::read_binary(&mut binary::open_file(path)?);
+        }
+        let serialized_build = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Error reading program build {}: {e}", path.display()))?;
+        Ok(serde_json::from_str(&serialized_build)?)
+    }
+
+    /// Writes the program build in the binary format, which is about half
+    /// the size of the JSON one.
+    pub fn write_to_binary_file(&self, path: &Path) -> Result<()> {
+        let mut writer = binary::create_file(path)?;
+        self.write_binary(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn write_binary<W: Write>(&self, writer: &mut W) -> Result<()> {
+        binary::write_header(writer, Kind::ProgramBuild)?;
+        binary::write_length(writer, self.map.len())?;
+        for (function_name, (proving_key, verifying_key)) in &self.map {
+            binary::write_string(writer, &function_name.to_string())?;
+            binary::write_canonical(writer, proving_key)?;
+            binary::write_canonical(writer, verifying_key)?;
+        }
+        Ok(())
+    }
+
+    pub fn read_binary<R: Read>(reader: &mut R) -> Result<Self> {
+        binary::read_header(reader, Kind::ProgramBuild)?;
+        let mut map = IndexMap::new();
+        for _ in 0..binary::read_length(reader)? {
+            let function_name = Identifier::from_str(&binary::read_string(reader)?)?;
+            let proving_key = binary::read_canonical(reader)?;
+            let verifying_key = binary::read_canonical(reader)?;
+            map.insert(function_name, (proving_key, verifying_key));
+        }
+        Ok(Self { map })
+    }
 }
 
 impl Serialize for ProgramBuild {
@@ -78,6 +78,21 @@ impl PartialEq for Record {
                         Ok(v) => v.value().unwrap_or(false),
                         Err(_) => false,
                     },
+                    (CircuitIOType::SimpleStruct(self_v), CircuitIOType::SimpleStruct(other_v)) => {
+                        self_v.len() == other_v.len()
+                            && self_v.iter().zip(other_v).all(
+                                |((self_member, self_value), (other_member, other_value))| {
+                                    self_member == other_member
+                                        && self_value.value().ok() == other_value.value().ok()
+                                },
+                            )
+                    }
+                    (CircuitIOType::SimpleString(self_v), CircuitIOType::SimpleString(other_v)) => {
+                        match self_v.is_eq(other_v) {
+                            Ok(v) => v.value().unwrap_or(false),
+                            Err(_) => false,
+                        }
+                    }
                     (_, _) => false,
                 };
                 let keys_are_equal = *self_k == *other_k;
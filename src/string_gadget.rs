@@ -0,0 +1,196 @@
+use anyhow::Result;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, AllocationMode, Boolean, EqGadget, FieldVar},
+    R1CSVar, ToBitsGadget, ToBytesGadget,
+};
+use ark_relations::r1cs::{Namespace, SynthesisError};
+use simpleworks::gadgets::{traits::IsWitness, ConstraintF, UInt8Gadget};
+use std::borrow::Borrow;
+
+/// Maximum number of bytes a string can have, same as snarkVM's.
+pub const MAX_STRING_BYTES: usize = 255;
+
+/// A string of at most `MAX_STRING_BYTES` bytes. The bytes are always padded
+/// with zeros up to `MAX_STRING_BYTES` so every string has the same number of
+/// variables, and the padding is enforced to be zero so two strings with the
+/// same value are also equal in the circuit.
+#[derive(Clone, Debug)]
+pub struct StringGadget {
+    length: UInt8Gadget,
+    bytes: Vec<UInt8Gadget>,
+}
+
+impl StringGadget {
+    pub fn is_witness(&self) -> Result<bool> {
+        // UInt8 gadget does not implement ToBytesGadget which is needed
+        // by IsWitness implementors but [UInt8] does.
+        [self.length.clone()].is_witness()
+    }
+}
+
+impl AllocVar<String, ConstraintF> for StringGadget {
+    fn new_variable<T: Borrow<String>>(
+        cs: impl Into<Namespace<ConstraintF>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        let value = f().map(|string| string.borrow().clone());
+        if let Ok(string) = &value {
+            if string.len() > MAX_STRING_BYTES {
+                return Err(SynthesisError::Unsatisfiable);
+            }
+        }
+
+        let length = UInt8Gadget::new_variable(
+            cs.clone(),
+            || {
+                value.clone().and_then(|string| {
+                    u8::try_from(string.len()).or(Err(SynthesisError::Unsatisfiable))
+                })
+            },
+            mode,
+        )?;
+        let bytes = Vec::<UInt8Gadget>::new_variable(
+            cs.clone(),
+            || {
+                value.clone().map(|string| {
+                    let mut bytes = string.into_bytes();
+                    bytes.resize(MAX_STRING_BYTES, 0);
+                    bytes
+                })
+            },
+            mode,
+        )?;
+
+        // mask[i] is true iff i < length. The mask is enforced to be a run of
+        // trues followed by falses adding up to the length, and the bytes
+        // outside of it are enforced to be zero.
+        // The mask is an auxiliary value so it is never a public input.
+        let mask_mode = if mode == AllocationMode::Constant {
+            AllocationMode::Constant
+        } else {
+            AllocationMode::Witness
+        };
+        let mut mask = Vec::with_capacity(MAX_STRING_BYTES);
+        for index in 0..MAX_STRING_BYTES {
+            let is_in_string = Boolean::new_variable(
+                cs.clone(),
+                || {
+                    value
+                        .as_ref()
+                        .map(|string| index < string.len())
+                        .map_err(Clone::clone)
+                },
+                mask_mode,
+            )?;
+            mask.push(is_in_string);
+        }
+
+        let mut mask_sum = FpVar::<ConstraintF>::zero();
+        let mut previous: Boolean<ConstraintF> = Boolean::TRUE;
+        for (is_in_string, byte) in mask.iter().zip(&bytes) {
+            is_in_string
+                .and(&previous.not())?
+                .enforce_equal(&Boolean::FALSE)?;
+            byte.conditional_enforce_equal(&UInt8Gadget::constant(0), &is_in_string.not())?;
+            mask_sum += FpVar::from(is_in_string.clone());
+            previous = is_in_string.clone();
+        }
+        mask_sum.enforce_equal(&Boolean::le_bits_to_fp_var(&length.to_bits_le()?)?)?;
+
+        Ok(Self { length, bytes })
+    }
+}
+
+impl R1CSVar<ConstraintF> for StringGadget {
+    type Value = String;
+
+    fn cs(&self) -> ark_relations::r1cs::ConstraintSystemRef<ConstraintF> {
+        self.length.cs().or(self.bytes.as_slice().cs())
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        let length = usize::from(self.length.value()?);
+        let bytes = self.bytes.as_slice().value()?;
+        let string_bytes = bytes
+            .get(..length)
+            .ok_or(SynthesisError::Unsatisfiable)?
+            .to_vec();
+        String::from_utf8(string_bytes).or(Err(SynthesisError::Unsatisfiable))
+    }
+}
+
+impl EqGadget<ConstraintF> for StringGadget {
+    fn is_eq(&self, other: &Self) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        self.length
+            .is_eq(&other.length)?
+            .and(&self.bytes.as_slice().is_eq(other.bytes.as_slice())?)
+    }
+}
+
+impl ToBytesGadget<ConstraintF> for StringGadget {
+    fn to_bytes(&self) -> Result<Vec<UInt8Gadget>, SynthesisError> {
+        let mut bytes = vec![self.length.clone()];
+        bytes.extend_from_slice(&self.bytes);
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod string_gadget_tests {
+    use super::{StringGadget, MAX_STRING_BYTES};
+    use ark_r1cs_std::{
+        prelude::{AllocVar, EqGadget},
+        R1CSVar,
+    };
+    use ark_relations::r1cs::ConstraintSystem;
+    use simpleworks::gadgets::ConstraintF;
+
+    #[test]
+    fn test_string_value() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_string = "hello world".to_owned();
+
+        let string =
+            StringGadget::new_witness(cs.clone(), || Ok(primitive_string.clone())).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(string.value().unwrap(), primitive_string);
+    }
+
+    #[test]
+    fn test_empty_string_value() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+
+        let string = StringGadget::new_input(cs.clone(), || Ok(String::new())).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(string.value().unwrap(), "");
+        assert!(!string.is_witness().unwrap());
+    }
+
+    #[test]
+    fn test_string_equality() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+
+        let hello = StringGadget::new_witness(cs.clone(), || Ok("hello".to_owned())).unwrap();
+        let other_hello = StringGadget::new_constant(cs.clone(), "hello".to_owned()).unwrap();
+        let hell = StringGadget::new_witness(cs.clone(), || Ok("hell".to_owned())).unwrap();
+
+        assert!(hello.is_eq(&other_hello).unwrap().value().unwrap());
+        assert!(!hello.is_eq(&hell).unwrap().value().unwrap());
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_string_that_is_too_long() {
+        let cs = ConstraintSystem::<ConstraintF>::new_ref();
+        let primitive_string = "a".repeat(MAX_STRING_BYTES + 1);
+
+        assert!(StringGadget::new_witness(cs, || Ok(primitive_string)).is_err());
+    }
+}
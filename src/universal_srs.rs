@@ -1,58 +1,476 @@
-use anyhow::{anyhow, Result};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Write};
-use simpleworks::marlin::UniversalSRS;
+use anyhow::{anyhow, bail, ensure, Result};
+use ark_marlin::AHPForR1CS;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use simpleworks::{gadgets::ConstraintF, marlin::UniversalSRS};
+use std::fmt::Display;
 use std::fs;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Environment variable that overrides where the universal parameters are
+/// stored.
+pub const UNIVERSAL_SRS_PATH_ENV: &str = "LAMBDAVM_UNIVERSAL_SRS_PATH";
+/// Environment variable that sets how thoroughly the stored universal
+/// parameters are checked when loaded, either `unchecked` or `checked`.
+pub const UNIVERSAL_SRS_LOAD_MODE_ENV: &str = "LAMBDAVM_UNIVERSAL_SRS_LOAD_MODE";
+
+/// Bounds the universal parameters are generated for by default.
+pub const DEFAULT_NUM_CONSTRAINTS: usize = 100000;
+pub const DEFAULT_NUM_VARIABLES: usize = 25000;
+pub const DEFAULT_NUM_NON_ZERO: usize = 300000;
+
+/// Size of the largest circuit some universal parameters can be used for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct UniversalSRSBounds {
+    pub num_constraints: usize,
+    pub num_variables: usize,
+    /// Non zero entries of the densest of the A, B and C matrices.
+    pub num_non_zero: usize,
+}
+
+impl UniversalSRSBounds {
+    pub const DEFAULT: Self = Self {
+        num_constraints: DEFAULT_NUM_CONSTRAINTS,
+        num_variables: DEFAULT_NUM_VARIABLES,
+        num_non_zero: DEFAULT_NUM_NON_ZERO,
+    };
+
+    /// Whether parameters generated for these bounds are big enough for a
+    /// circuit of the `required` size. Marlin makes the matrices square
+    /// before indexing them, so only the largest of the number of constraints
+    /// and variables matters.
+    pub fn contains(&self, required: &Self) -> bool {
+        self.num_constraints.max(self.num_variables)
+            >= required.num_constraints.max(required.num_variables)
+            && self.num_non_zero >= required.num_non_zero
+    }
+
+    /// Smallest bounds that contain both `self` and `other`.
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            num_constraints: self.num_constraints.max(other.num_constraints),
+            num_variables: self.num_variables.max(other.num_variables),
+            num_non_zero: self.num_non_zero.max(other.num_non_zero),
+        }
+    }
+}
+
+impl Display for UniversalSRSBounds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} constraints, {} variables and {} non zero entries",
+            self.num_constraints, self.num_variables, self.num_non_zero
+        )
+    }
+}
+
+/// Identifies a universal parameters file.
+const MAGIC: [u8; 8] = *b"LVM_SRS\0";
+/// Version of the universal parameters file format.
+pub const UNIVERSAL_SRS_FILE_VERSION: u32 = 1;
+const HEADER_LENGTH: usize = 8 + 4 + 3 * 8 + 32;
+
+/// How thoroughly the universal parameters are checked when loaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoadMode {
+    /// The header and the checksum are checked, the group elements are
+    /// trusted as they are.
+    Unchecked,
+    /// The checksum is verified and every group element is validated, which
+    /// is slower.
+    Checked,
+}
+
+impl LoadMode {
+    /// Mode set by `LAMBDAVM_UNIVERSAL_SRS_LOAD_MODE`, `Unchecked` if it is
+    /// not set.
+    pub fn from_env() -> Result<Self> {
+        match std::env::var(UNIVERSAL_SRS_LOAD_MODE_ENV) {
+            Ok(mode) => mode.parse(),
+            Err(std::env::VarError::NotPresent) => Ok(Self::Unchecked),
+            Err(e) => bail!("Invalid {UNIVERSAL_SRS_LOAD_MODE_ENV}: {e}"),
+        }
+    }
+}
+
+impl FromStr for LoadMode {
+    type Err = anyhow::Error;
+
+    fn from_str(mode: &str) -> Result<Self> {
+        match mode {
+            "unchecked" => Ok(Self::Unchecked),
+            "checked" => Ok(Self::Checked),
+            _ => bail!(
+                "Invalid universal parameters load mode {mode}, expected `unchecked` or `checked`"
+            ),
+        }
+    }
+}
+
+/// Header written before the universal parameters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UniversalSRSHeader {
+    pub version: u32,
+    /// Bounds the parameters were generated for.
+    pub bounds: UniversalSRSBounds,
+    /// SHA-256 of the serialized parameters.
+    pub checksum: [u8; 32],
+}
+
+impl UniversalSRSHeader {
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        for bound in [
+            self.bounds.num_constraints,
+            self.bounds.num_variables,
+            self.bounds.num_non_zero,
+        ] {
+            bytes.extend_from_slice(&u64::try_from(bound)?.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.checksum);
+        Ok(bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let field = |range: std::ops::Range<usize>| {
+            bytes
+                .get(range)
+                .ok_or_else(|| anyhow!("Universal parameters file is too short"))
+        };
+        ensure!(
+            field(0..8)? == MAGIC,
+            "Not a universal parameters file, or one from an older version. Generate them again"
+        );
+        let version = u32::from_le_bytes(field(8..12)?.try_into()?);
+        ensure!(
+            version == UNIVERSAL_SRS_FILE_VERSION,
+            "Unsupported universal parameters file version {version}, expected {UNIVERSAL_SRS_FILE_VERSION}"
+        );
+        let bound = |start: usize| -> Result<usize> {
+            Ok(usize::try_from(u64::from_le_bytes(
+                field(start..start + 8)?.try_into()?,
+            ))?)
+        };
+        Ok(Self {
+            version,
+            bounds: UniversalSRSBounds {
+                num_constraints: bound(12)?,
+                num_variables: bound(20)?,
+                num_non_zero: bound(28)?,
+            },
+            checksum: field(36..HEADER_LENGTH)?.try_into()?,
+        })
+    }
+}
 
 /// Note: this function will always generate the same universal parameters because
 /// the rng seed is hardcoded. This is not going to be the case forever, though, as eventually
 /// these parameters will be something generated in a setup ceremony and thus it will not be possible
 /// to derive them deterministically like this.
 pub fn generate_universal_srs() -> Result<Box<UniversalSRS>> {
+    generate_universal_srs_with_bounds(&UniversalSRSBounds::DEFAULT)
+}
+
+/// Same as `generate_universal_srs` but for circuits of the given size.
+pub fn generate_universal_srs_with_bounds(
+    bounds: &UniversalSRSBounds,
+) -> Result<Box<UniversalSRS>> {
     let rng = &mut simpleworks::marlin::generate_rand();
-    simpleworks::marlin::generate_universal_srs(100000, 25000, 300000, rng)
+    simpleworks::marlin::generate_universal_srs(
+        bounds.num_constraints,
+        bounds.num_variables,
+        bounds.num_non_zero,
+        rng,
+    )
 }
 
-pub fn get_universal_srs_dir_and_filepath() -> Result<(PathBuf, PathBuf)> {
-    let parameters_dir = dirs::home_dir()
+/// Keeps only the part of the universal parameters needed for circuits of the
+/// given size, which is a prefix of the powers they hold. Keys built from
+/// parameters trimmed to the same bounds are the same whatever the size of
+/// the parameters they were trimmed from.
+pub fn trim_universal_srs(
+    universal_srs: &UniversalSRS,
+    bounds: &UniversalSRSBounds,
+) -> Result<Box<UniversalSRS>> {
+    let max_degree = AHPForR1CS::<ConstraintF>::max_degree(
+        bounds.num_constraints,
+        bounds.num_variables,
+        bounds.num_non_zero,
+    )
+    .map_err(|e| anyhow!("Error computing the degree of the universal parameters: {e:?}"))?;
+    ensure!(
+        max_degree < universal_srs.powers_of_g.len(),
+        "The universal parameters are too small for {bounds}"
+    );
+
+    let mut trimmed = universal_srs.clone();
+    trimmed.powers_of_g.truncate(max_degree + 1);
+    trimmed
+        .powers_of_gamma_g
+        .retain(|degree, _| *degree <= max_degree + 1);
+    trimmed
+        .neg_powers_of_h
+        .retain(|degree, _| *degree <= max_degree);
+    Ok(Box::new(trimmed))
+}
+
+/// Directory where lambdavm stores its files.
+pub fn lambdavm_dir() -> Result<PathBuf> {
+    Ok(dirs::home_dir()
         .ok_or_else(|| anyhow!("Home dir not found. Set a home directory"))?
-        .join(".lambdavm");
+        .join(".lambdavm"))
+}
 
-    let file_dir = parameters_dir.join("universal_srs");
+/// Returns the directory and the path of the universal parameters file, which
+/// is `~/.lambdavm/universal_srs` unless `LAMBDAVM_UNIVERSAL_SRS_PATH` is set.
+pub fn get_universal_srs_dir_and_filepath() -> Result<(PathBuf, PathBuf)> {
+    let file_dir = match std::env::var_os(UNIVERSAL_SRS_PATH_ENV) {
+        Some(path) => PathBuf::from(path),
+        None => lambdavm_dir()?.join("universal_srs"),
+    };
+    let parameters_dir = file_dir.parent().map(Path::to_path_buf).unwrap_or_default();
     Ok((parameters_dir, file_dir))
 }
 
+/// Loads the stored universal parameters, checked as set by
+/// `LAMBDAVM_UNIVERSAL_SRS_LOAD_MODE`.
 pub fn load_universal_srs_from_file() -> Result<Box<UniversalSRS>> {
     let (_parameters_dir, file_dir) = get_universal_srs_dir_and_filepath()?;
-    let f = fs::File::open(file_dir)?;
-    let reader = BufReader::new(f);
-    let des = UniversalSRS::deserialize_unchecked(reader);
+    load_universal_srs_from_path(&file_dir, LoadMode::from_env()?)
+}
 
-    Ok(Box::new(des.map_err(|_e| {
-        anyhow!("Error deserializing Universal SRS")
-    })?))
+/// Reads the header of a universal parameters file, without reading the
+/// parameters that follow it.
+pub fn read_universal_srs_header(path: &Path) -> Result<UniversalSRSHeader> {
+    let file = fs::File::open(path)
+        .map_err(|e| anyhow!("Error reading universal parameters {}: {e}", path.display()))?;
+    let mut bytes = Vec::with_capacity(HEADER_LENGTH);
+    file.take(u64::try_from(HEADER_LENGTH)?)
+        .read_to_end(&mut bytes)
+        .map_err(|e| anyhow!("Error reading universal parameters {}: {e}", path.display()))?;
+    UniversalSRSHeader::from_bytes(&bytes)
 }
 
-pub fn generate_universal_srs_and_write_to_file() -> Result<PathBuf> {
-    let universal_srs = generate_universal_srs()?;
+pub fn load_universal_srs_from_path(path: &Path, mode: LoadMode) -> Result<Box<UniversalSRS>> {
+    let bytes = fs::read(path)
+        .map_err(|e| anyhow!("Error reading universal parameters {}: {e}", path.display()))?;
+    let header = UniversalSRSHeader::from_bytes(&bytes)?;
+    let serialized_srs = bytes
+        .get(HEADER_LENGTH..)
+        .ok_or_else(|| anyhow!("Universal parameters file is too short"))?;
 
-    let mut bytes = Vec::new();
-    universal_srs.serialize_uncompressed(&mut bytes)?;
+    let checksum: [u8; 32] = Sha256::digest(serialized_srs).into();
+    ensure!(
+        checksum == header.checksum,
+        "Universal parameters checksum mismatch, the file is corrupted"
+    );
+    let universal_srs = match mode {
+        LoadMode::Unchecked => UniversalSRS::deserialize_unchecked(serialized_srs),
+        LoadMode::Checked => UniversalSRS::deserialize_uncompressed(serialized_srs),
+    }
+    .map_err(|e| anyhow!("Error deserializing Universal SRS: {e}"))?;
 
-    let (parameters_dir, file_dir) = get_universal_srs_dir_and_filepath()?;
-    fs::create_dir_all(parameters_dir)?;
+    Ok(Box::new(universal_srs))
+}
+
+fn serialize_universal_srs(universal_srs: &UniversalSRS) -> Result<Vec<u8>> {
+    let mut serialized_srs = Vec::new();
+    universal_srs.serialize_uncompressed(&mut serialized_srs)?;
+    Ok(serialized_srs)
+}
+
+/// SHA-256 of the universal parameters, the same one stored in the header
+/// of their file.
+pub fn universal_srs_checksum(universal_srs: &UniversalSRS) -> Result<[u8; 32]> {
+    Ok(Sha256::digest(serialize_universal_srs(universal_srs)?).into())
+}
 
-    let mut file = std::fs::OpenOptions::new()
-        // create or open if it already exists
-        .create(true)
-        .write(true)
-        // Overwrite file, do not append
-        .append(false)
-        .open(&file_dir)?;
+/// Writes the universal parameters to the given path, preceded by a header
+/// with the bounds they were generated for and their checksum.
+pub fn write_universal_srs_to_path(
+    universal_srs: &UniversalSRS,
+    bounds: &UniversalSRSBounds,
+    path: &Path,
+) -> Result<()> {
+    let serialized_srs = serialize_universal_srs(universal_srs)?;
 
-    // This let is so clippy doesn't complain
-    let _written_amount = file.write(&bytes)?;
+    let header = UniversalSRSHeader {
+        version: UNIVERSAL_SRS_FILE_VERSION,
+        bounds: *bounds,
+        checksum: Sha256::digest(&serialized_srs).into(),
+    };
+    let mut bytes = header.to_bytes()?;
+    bytes.extend(serialized_srs);
 
+    if let Some(parameters_dir) = path.parent() {
+        fs::create_dir_all(parameters_dir)?;
+    }
+    // Overwrite the file if it already exists.
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+pub fn generate_universal_srs_and_write_to_file() -> Result<PathBuf> {
+    let (_parameters_dir, file_dir) = get_universal_srs_dir_and_filepath()?;
+    generate_universal_srs_and_write_to_path(&file_dir, &UniversalSRSBounds::DEFAULT)?;
     Ok(file_dir)
 }
+
+pub fn generate_universal_srs_and_write_to_path(
+    path: &Path,
+    bounds: &UniversalSRSBounds,
+) -> Result<()> {
+    let universal_srs = generate_universal_srs_with_bounds(bounds)?;
+    write_universal_srs_to_path(&universal_srs, bounds, path)
+}
+
+/// Fails with the size the universal parameters need to have if the ones
+/// generated for `bounds` are too small for a circuit of the `required`
+/// size.
+pub fn ensure_universal_srs_fits(
+    bounds: &UniversalSRSBounds,
+    required: &UniversalSRSBounds,
+) -> Result<()> {
+    ensure!(
+        bounds.contains(required),
+        "The universal parameters were generated for {bounds} but at least {required} are needed. Generate bigger ones with `generate_parameters --num-constraints {} --num-variables {} --num-non-zero {}`",
+        required.num_constraints,
+        required.num_variables,
+        required.num_non_zero
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod universal_srs_tests {
+    use super::{
+        ensure_universal_srs_fits, load_universal_srs_from_path, read_universal_srs_header,
+        trim_universal_srs, write_universal_srs_to_path, LoadMode, UniversalSRSBounds,
+        HEADER_LENGTH, UNIVERSAL_SRS_FILE_VERSION,
+    };
+    use ark_serialize::CanonicalSerialize;
+    use std::{path::PathBuf, str::FromStr};
+
+    const SMALL_BOUNDS: UniversalSRSBounds = UniversalSRSBounds {
+        num_constraints: 100,
+        num_variables: 25,
+        num_non_zero: 300,
+    };
+
+    fn write_small_universal_srs(file_name: &str) -> (PathBuf, Vec<u8>) {
+        let universal_srs = super::generate_universal_srs_with_bounds(&SMALL_BOUNDS).unwrap();
+        let path = std::env::temp_dir().join(file_name);
+        write_universal_srs_to_path(&universal_srs, &SMALL_BOUNDS, &path).unwrap();
+
+        let mut serialized_srs = Vec::new();
+        universal_srs
+            .serialize_uncompressed(&mut serialized_srs)
+            .unwrap();
+        (path, serialized_srs)
+    }
+
+    #[test]
+    fn test_write_and_load_universal_srs() {
+        let (path, serialized_srs) = write_small_universal_srs("lambdavm_test_srs");
+
+        let header = read_universal_srs_header(&path).unwrap();
+        assert_eq!(header.version, UNIVERSAL_SRS_FILE_VERSION);
+        assert_eq!(header.bounds, SMALL_BOUNDS);
+
+        for mode in [LoadMode::Unchecked, LoadMode::Checked] {
+            let universal_srs = load_universal_srs_from_path(&path, mode).unwrap();
+            let mut loaded_serialized_srs = Vec::new();
+            universal_srs
+                .serialize_uncompressed(&mut loaded_serialized_srs)
+                .unwrap();
+            assert_eq!(loaded_serialized_srs, serialized_srs);
+        }
+    }
+
+    #[test]
+    fn test_load_detects_corruption() {
+        let (path, _serialized_srs) = write_small_universal_srs("lambdavm_test_corrupted_srs");
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last_byte = bytes.last_mut().unwrap();
+        *last_byte ^= 1;
+        std::fs::write(&path, bytes).unwrap();
+
+        assert!(load_universal_srs_from_path(&path, LoadMode::Checked).is_err());
+        assert!(load_universal_srs_from_path(&path, LoadMode::Unchecked).is_err());
+    }
+
+    #[test]
+    fn test_parse_load_mode() {
+        assert_eq!(
+            LoadMode::from_str("unchecked").unwrap(),
+            LoadMode::Unchecked
+        );
+        assert_eq!(LoadMode::from_str("checked").unwrap(), LoadMode::Checked);
+        assert!(LoadMode::from_str("Checked").is_err());
+    }
+
+    #[test]
+    fn test_load_file_without_header() {
+        let path = std::env::temp_dir().join("lambdavm_test_srs_without_header");
+        std::fs::write(&path, [0_u8; 128]).unwrap();
+
+        assert!(load_universal_srs_from_path(&path, LoadMode::Unchecked).is_err());
+    }
+
+    #[test]
+    fn test_read_header_of_truncated_file() {
+        let (path, _serialized_srs) = write_small_universal_srs("lambdavm_test_truncated_srs");
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..HEADER_LENGTH]).unwrap();
+
+        let header = read_universal_srs_header(&path).unwrap();
+        assert_eq!(header.bounds, SMALL_BOUNDS);
+
+        std::fs::write(&path, &bytes[..HEADER_LENGTH - 1]).unwrap();
+        assert!(read_universal_srs_header(&path).is_err());
+    }
+
+    #[test]
+    fn test_trim_universal_srs() {
+        let universal_srs = super::generate_universal_srs_with_bounds(&SMALL_BOUNDS).unwrap();
+        let bounds = UniversalSRSBounds {
+            num_constraints: 10,
+            num_variables: 5,
+            num_non_zero: 20,
+        };
+        let trimmed = trim_universal_srs(&universal_srs, &bounds).unwrap();
+        let sized = super::generate_universal_srs_with_bounds(&bounds).unwrap();
+
+        assert!(trimmed.powers_of_g.len() < universal_srs.powers_of_g.len());
+        assert_eq!(trimmed.powers_of_g, sized.powers_of_g);
+        assert!(trim_universal_srs(&trimmed, &SMALL_BOUNDS).is_err());
+    }
+
+    #[test]
+    fn test_ensure_universal_srs_fits() {
+        // The matrices are made square, so more variables fit as long as
+        // there are fewer constraints.
+        let required = UniversalSRSBounds {
+            num_constraints: 50,
+            num_variables: 80,
+            num_non_zero: 300,
+        };
+        assert!(ensure_universal_srs_fits(&SMALL_BOUNDS, &required).is_ok());
+
+        let required = UniversalSRSBounds {
+            num_constraints: 120,
+            num_variables: 25,
+            num_non_zero: 200,
+        };
+        let error = ensure_universal_srs_fits(&SMALL_BOUNDS, &required).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("--num-constraints 120 --num-variables 25 --num-non-zero 200"));
+    }
+}
@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum VariableType {
+    /// The plaintext of a value hardcoded into the circuit.
+    Constant(UserInputValueType),
     /// The plaintext.
     Public(UserInputValueType),
     /// The ciphertext.
@@ -21,8 +23,9 @@ pub enum VariableType {
 impl VariableType {
     pub fn value(&self) -> Result<UserInputValueType> {
         match self {
-            // XXX::Constant(_, value) => Ok(value.to_string()),
-            VariableType::Public(value) | VariableType::Private(value) => Ok(value.clone()),
+            VariableType::Constant(value)
+            | VariableType::Public(value)
+            | VariableType::Private(value) => Ok(value.clone()),
             VariableType::Record(
                 _,
                 Record {
@@ -47,7 +50,9 @@ impl VariableType {
 impl Display for VariableType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            VariableType::Public(v) | VariableType::Private(v) => UserInputValueType::fmt(v, f),
+            VariableType::Constant(v) | VariableType::Public(v) | VariableType::Private(v) => {
+                UserInputValueType::fmt(v, f)
+            }
             VariableType::Record(_, v) => Record::fmt(v, f),
             VariableType::EncryptedRecord((_commitment, encrypted_record)) => {
                 EncryptedRecord::fmt(encrypted_record, f)
@@ -73,6 +78,15 @@ mod tests {
         assert_eq!(serialized_public_variable, r#"{"Public":"1u8"}"#);
     }
 
+    #[test]
+    fn test_serialize_constant_variable_type() {
+        let constant_variable = VariableType::Constant(UserInputValueType::U8(1));
+
+        let serialized_constant_variable = serde_json::to_string(&constant_variable).unwrap();
+
+        assert_eq!(serialized_constant_variable, r#"{"Constant":"1u8"}"#);
+    }
+
     #[test]
     fn test_serialize_private_variable_type() {
         let private_variable = VariableType::Private(UserInputValueType::U8(1));
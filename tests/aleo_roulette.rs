@@ -164,7 +164,7 @@ mod aleo_roulette_functions_tests {
         let mut casino_token_record_data = jaleo::RecordEntriesMap::new();
         casino_token_record_data.insert(
             "amount".to_owned(),
-            lambdavm::jaleo::UserInputValueType::U64(casino_token_record_amount),
+            lambdavm::jaleo::UserInputValueType::U64(casino_token_record_amount).into(),
         );
         let casino_token_record_nonce = helpers::random_nonce();
 
@@ -564,16 +564,19 @@ mod aleo_roulette_functions_tests {
         if let lambdavm::CircuitIOType::SimpleRecord(record) = r2 {
             assert_eq!(record.owner.value().unwrap(), address_string);
             assert_eq!(record.gates.value().unwrap(), 0);
-            // assert_ne!(record.nonce, ConstraintF::default());
+            // The amount is a public entry.
+            assert!(!record.entries["amount"].is_witness().unwrap());
         } else {
             panic!("r2 should be a record");
         }
 
+        // The public entries of the minted record follow the public inputs.
         assert_that_proof_for_function_execution_is_correct(
             program,
             &[
                 jaleo::UserInputValueType::U64(amount_to_mint),
                 jaleo::UserInputValueType::Address(address_bytes),
+                jaleo::UserInputValueType::U64(amount_to_mint),
             ],
             &proof,
             "mint",
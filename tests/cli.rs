@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod cli_tests {
+    use lambdavm::jaleo::Account;
+    use serde_json::Value;
+    use std::{path::Path, process::Command};
+
+    #[ctor::ctor]
+    fn init() {
+        // generate universal srs file before running tests
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+    }
+
+    /// Runs the CLI with the given arguments, which must succeed, and returns
+    /// what it printed to stdout.
+    fn lambdavm(args: &[&str]) -> String {
+        let output = Command::new(env!("CARGO_BIN_EXE_lambdavm"))
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "lambdavm {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn test_execute_scan_and_verify_transitions() {
+        let program_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("programs/records.aleo");
+        let program_path = program_path.to_str().unwrap();
+        let dir = std::env::temp_dir().join("lambdavm_test_cli_transitions");
+        std::fs::create_dir_all(&dir).unwrap();
+        let deployment_path = dir.join("deployment.json");
+        let deployment_path = deployment_path.to_str().unwrap();
+        let transitions_path = dir.join("transitions.jsonl");
+        let account = Account::new(Some(1)).unwrap();
+
+        lambdavm(&["deploy", program_path, "-o", deployment_path]);
+
+        let transitions = lambdavm(&[
+            "execute",
+            "mint",
+            program_path,
+            "--transitions",
+            "--private-key",
+            &account.private_key.to_string(),
+            "--encryption",
+            "poseidon",
+            "5u64",
+            &account.address.to_string(),
+        ]);
+        assert_eq!(transitions.lines().count(), 1);
+        std::fs::write(&transitions_path, transitions).unwrap();
+        let transitions_path = transitions_path.to_str().unwrap();
+
+        let records: Value = serde_json::from_str(&lambdavm(&[
+            "record",
+            "scan",
+            transitions_path,
+            "--view-key",
+            &account.view_key.to_string(),
+            "--format",
+            "json",
+        ]))
+        .unwrap();
+        assert_eq!(records["records"].as_array().map(Vec::len), Some(1));
+
+        lambdavm(&[
+            "verify-transition",
+            transitions_path,
+            "--deployment",
+            deployment_path,
+        ]);
+    }
+}
@@ -75,6 +75,34 @@ mod credits_functions_tests {
         )
     }
 
+    #[test]
+    fn test_owned_records() {
+        let owner = jaleo::Account::new(Some(1)).unwrap();
+        let other = jaleo::Account::new(Some(2)).unwrap();
+        let (commitment, encrypted_record) = jaleo::mint_credits(&owner.address, 10, 1).unwrap();
+
+        let transition = jaleo::Transition {
+            program_id: jaleo::ProgramID::from_str("credits.aleo").unwrap(),
+            function_name: jaleo::Identifier::from_str("mint").unwrap(),
+            inputs: vec![],
+            outputs: vec![lambdavm::VariableType::EncryptedRecord((
+                commitment.clone(),
+                encrypted_record,
+            ))],
+            proof: String::new(),
+            fee: 0,
+        };
+
+        let owned_records = transition.owned_records(&owner.view_key);
+        assert_eq!(owned_records.len(), 1);
+        let (owned_commitment, record) = owned_records.first().unwrap();
+        assert_eq!(*owned_commitment, commitment);
+        assert_eq!(record.gates, 10);
+        assert_eq!(record.commitment().unwrap(), commitment);
+
+        assert!(transition.owned_records(&other.view_key).is_empty());
+    }
+
     #[test]
     fn test_mint() {
         let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -0,0 +1,57 @@
+mod helpers;
+
+#[cfg(test)]
+mod debugger_tests {
+    use super::helpers::test_helpers;
+    use lambdavm::{
+        debugger::Debugger,
+        jaleo::{Program, UserInputValueType::U16},
+    };
+    use snarkvm::prelude::Parser;
+
+    #[test]
+    fn test_step_through_function() {
+        let program_string = test_helpers::read_program("add").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+
+        /*
+        function hello_1:
+            input r0 as u16.public;
+            input r1 as u16.public;
+            add r0 r1 into r2;
+            output r2 as u16.public;
+        */
+
+        let user_inputs = vec![U16(1), U16(2)];
+        let mut debugger = Debugger::new(&program, &[], "hello_1", &user_inputs).unwrap();
+        assert_eq!(debugger.next_instruction(), 0);
+        assert!(debugger.registers()["r2"].is_none());
+
+        let step = debugger.step().unwrap().unwrap();
+        assert_eq!(step.index, 0);
+        assert_eq!(step.instruction, "add r0 r1 into r2;");
+        assert_eq!(step.destination, Some("r2".to_owned()));
+        assert!(step.constraints > 0);
+        assert!(step.satisfied);
+        assert_eq!(
+            debugger.registers()["r2"]
+                .as_ref()
+                .unwrap()
+                .value()
+                .unwrap(),
+            "3"
+        );
+
+        assert!(debugger.is_finished());
+        assert!(debugger.step().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_debugger_with_invalid_inputs() {
+        let program_string = test_helpers::read_program("add").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+
+        assert!(Debugger::new(&program, &[], "hello_1", &[U16(1)]).is_err());
+        assert!(Debugger::new(&program, &[], "missing", &[U16(1), U16(2)]).is_err());
+    }
+}
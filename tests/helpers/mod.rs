@@ -32,6 +32,13 @@ pub mod test_helpers {
         Ok(program)
     }
 
+    pub fn read_import(instruction: &str, import: &str) -> Result<String> {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push(format!("programs/{instruction}/{import}"));
+        let program = std::fs::read_to_string(path).unwrap_or_else(|_| "".to_owned());
+        Ok(program)
+    }
+
     pub fn input_record(
         owner: jaleo::AddressBytes,
         gates: u64,
@@ -117,7 +124,7 @@ pub mod test_helpers {
         };
         for (function_name, function) in program.functions() {
             let constraint_system = ConstraintSystem::<ConstraintF>::new_ref();
-            let inputs = default_user_inputs_for_div(&program, function_name)?;
+            let inputs = default_user_inputs_for_div(&program, &[], function_name)?;
             let (function_proving_key, function_verifying_key) = match build_function(
                 &program,
                 function,
@@ -149,6 +156,7 @@ pub mod test_helpers {
     // NOTE: Same as above.
     pub fn default_user_inputs_for_div(
         program: &Program,
+        imports: &[Program],
         function_name: &Identifier,
     ) -> Result<Vec<UserInputValueType>> {
         let mut default_user_inputs: Vec<UserInputValueType> = Vec::new();
@@ -209,14 +217,24 @@ pub mod test_helpers {
                     UserInputValueType::Record(Record {
                         owner: *b"aleo11111111111111111111111111111111111111111111111111111111111",
                         gates: u64::default(),
-                        data: aleo_entries_to_vm_entries(aleo_record_entries)?,
+                        data: aleo_entries_to_vm_entries(program, aleo_record_entries)?,
                         nonce: Some(helpers::random_nonce()),
                     })
                 }
                 // Constant Types
                 ValueType::Constant(_) => bail!("Constant types are not supported"),
                 // External Records
-                ValueType::ExternalRecord(_) => bail!("ExternalRecord types are not supported"),
+                ValueType::ExternalRecord(locator) => {
+                    let external_program = helpers::external_program(program, imports, locator)?;
+                    let aleo_record = external_program.get_record(locator.resource())?;
+                    let aleo_record_entries = aleo_record.entries();
+                    UserInputValueType::Record(Record {
+                        owner: *b"aleo11111111111111111111111111111111111111111111111111111111111",
+                        gates: u64::default(),
+                        data: aleo_entries_to_vm_entries(external_program, aleo_record_entries)?,
+                        nonce: Some(helpers::random_nonce()),
+                    })
+                }
             };
             default_user_inputs.push(default_user_input);
         }
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod constant_tests {
+    use crate::helpers::test_helpers;
+    use lambdavm::jaleo::UserInputValueType::{U16, U8};
+    use snarkvm::prelude::{Parser, Program, Testnet3};
+
+    #[test]
+    fn test_constant_inputs_are_circuit_constants() {
+        let program_string = test_helpers::read_program("constant").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_1";
+
+        /*
+        function hello_1:
+            input r0 as u8.constant;
+            input r1 as u8.public;
+            add r0 r1 into r2;
+            output r2 as u8.public;
+        */
+
+        let user_inputs = vec![U8(1), U8(2)];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r0 = function_variables["r0"].as_ref().unwrap();
+        assert!(matches!(r0, lambdavm::CircuitIOType::SimpleUInt8(_)));
+        assert!(r0.is_constant());
+        assert_eq!(r0.value().unwrap(), "1".to_owned());
+
+        let r2 = function_variables["r2"].as_ref().unwrap();
+        assert!(matches!(r2, lambdavm::CircuitIOType::SimpleUInt8(_)));
+        assert_eq!(r2.value().unwrap(), "3".to_owned());
+    }
+
+    #[test]
+    fn test_constant_output() {
+        let program_string = test_helpers::read_program("constant").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_2";
+
+        /*
+        function hello_2:
+            input r0 as u8.constant;
+            input r1 as u8.constant;
+            add r0 r1 into r2;
+            output r2 as u8.constant;
+        */
+
+        let user_inputs = vec![U8(1), U8(2)];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r2 = function_variables["r2"].as_ref().unwrap();
+        assert!(r2.is_constant());
+        assert_eq!(r2.value().unwrap(), "3".to_owned());
+    }
+
+    #[test]
+    fn test_constant_output_that_depends_on_a_witness() {
+        let program_string = test_helpers::read_program("constant").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_3";
+
+        /*
+        function hello_3:
+            input r0 as u8.constant;
+            input r1 as u8.private;
+            add r0 r1 into r2;
+            output r2 as u8.constant;
+        */
+
+        let user_inputs = vec![U8(1), U8(2)];
+
+        assert!(lambdavm::execute_function(&program, function_name, &user_inputs).is_err());
+    }
+
+    #[test]
+    fn test_constant_input_with_mismatched_type() {
+        let program_string = test_helpers::read_program("constant").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_1";
+
+        let user_inputs = vec![U16(1), U8(2)];
+
+        assert!(lambdavm::execute_function(&program, function_name, &user_inputs).is_err());
+    }
+}
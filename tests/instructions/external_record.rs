@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod external_record_tests {
+    use crate::helpers::test_helpers;
+    use lambdavm::{
+        helpers,
+        jaleo::{RecordEntriesMap, UserInputValueType::U64},
+    };
+    use snarkvm::prelude::{Parser, Program, Testnet3};
+
+    fn programs() -> (Program<Testnet3>, Program<Testnet3>) {
+        let program_string = test_helpers::read_program("external_record").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let import_string = test_helpers::read_import("external_record", "token.aleo").unwrap();
+        let (_, import) = Program::<Testnet3>::parse(&import_string).unwrap();
+        (program, import)
+    }
+
+    #[test]
+    fn test_external_record_input() {
+        let (program, import) = programs();
+        let function_name = "hello_1";
+
+        /*
+        function hello_1:
+            input r0 as token.aleo/token.record;
+            input r1 as u64.public;
+            add r0.amount r1 into r2;
+            output r2 as u64.public;
+        */
+
+        let (_address_string, address) = test_helpers::address();
+        let mut data = RecordEntriesMap::new();
+        data.insert("amount".to_owned(), U64(1).into());
+        let user_inputs = vec![
+            test_helpers::input_record(address, 0, data, helpers::random_nonce()),
+            U64(2),
+        ];
+
+        // execute circuit
+        let (function_variables, _proof, _constraint_counts) =
+            lambdavm::execute_function_with_imports(
+                &program,
+                &[import],
+                function_name,
+                &user_inputs,
+            )
+            .unwrap();
+
+        let r0 = function_variables["r0"].as_ref().unwrap();
+        assert!(matches!(r0, lambdavm::CircuitIOType::SimpleRecord(_)));
+
+        let r2 = function_variables["r2"].as_ref().unwrap();
+        assert!(matches!(r2, lambdavm::CircuitIOType::SimpleUInt64(_)));
+        assert_eq!(r2.value().unwrap(), "3".to_owned());
+    }
+
+    #[test]
+    fn test_external_record_input_with_mismatched_entries() {
+        let (program, import) = programs();
+        let function_name = "hello_1";
+
+        let (_address_string, address) = test_helpers::address();
+        let mut data = RecordEntriesMap::new();
+        data.insert("balance".to_owned(), U64(1).into());
+        let user_inputs = vec![
+            test_helpers::input_record(address, 0, data, helpers::random_nonce()),
+            U64(2),
+        ];
+
+        assert!(lambdavm::execute_function_with_imports(
+            &program,
+            &[import],
+            function_name,
+            &user_inputs
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_external_record_input_without_imports() {
+        let (program, _import) = programs();
+        let function_name = "hello_1";
+
+        let (_address_string, address) = test_helpers::address();
+        let mut data = RecordEntriesMap::new();
+        data.insert("amount".to_owned(), U64(1).into());
+        let user_inputs = vec![
+            test_helpers::input_record(address, 0, data, helpers::random_nonce()),
+            U64(2),
+        ];
+
+        let error = lambdavm::execute_function(&program, function_name, &user_inputs).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Imported program token.aleo was not provided"
+        );
+    }
+}
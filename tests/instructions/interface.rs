@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod interface_tests {
+    use crate::helpers::test_helpers;
+    use lambdavm::{
+        helpers,
+        jaleo::{
+            Identifier, StructMembersMap,
+            UserInputValueType::{Struct, U64, U8},
+        },
+    };
+    use snarkvm::prelude::{Parser, Program, Testnet3};
+    use std::str::FromStr;
+
+    fn point(x: u64, y: u64) -> StructMembersMap {
+        let mut members = StructMembersMap::new();
+        members.insert("x".to_owned(), U64(x));
+        members.insert("y".to_owned(), U64(y));
+        members
+    }
+
+    #[test]
+    fn test_interface_public_input_member_access() {
+        let program_string = test_helpers::read_program("interface").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_1";
+
+        /*
+        function hello_1:
+            input r0 as point.public;
+            add r0.x r0.y into r1;
+            output r1 as u64.public;
+        */
+
+        let user_inputs = vec![Struct(point(1, 2))];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r0 = function_variables["r0"].as_ref().unwrap();
+        assert!(matches!(r0, lambdavm::CircuitIOType::SimpleStruct(_)));
+        assert_eq!(r0.value().unwrap(), "{ x: 1, y: 2 }".to_owned());
+        assert!(!r0.is_witness().unwrap());
+
+        let r1 = function_variables["r1"].as_ref().unwrap();
+        assert!(matches!(r1, lambdavm::CircuitIOType::SimpleUInt64(_)));
+        assert_eq!(r1.value().unwrap(), "3".to_owned());
+    }
+
+    #[test]
+    fn test_interface_private_input_member_access() {
+        let program_string = test_helpers::read_program("interface").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_2";
+
+        /*
+        function hello_2:
+            input r0 as point.private;
+            add r0.x r0.y into r1;
+            output r1 as u64.private;
+        */
+
+        let user_inputs = vec![Struct(point(3, 4))];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r0 = function_variables["r0"].as_ref().unwrap();
+        assert!(matches!(r0, lambdavm::CircuitIOType::SimpleStruct(_)));
+        assert!(r0.is_witness().unwrap());
+
+        let r1 = function_variables["r1"].as_ref().unwrap();
+        assert!(matches!(r1, lambdavm::CircuitIOType::SimpleUInt64(_)));
+        assert_eq!(r1.value().unwrap(), "7".to_owned());
+    }
+
+    #[test]
+    fn test_cast_into_interface() {
+        let program_string = test_helpers::read_program("interface").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_3";
+
+        /*
+        function hello_3:
+            input r0 as u64.private;
+            input r1 as u64.private;
+            cast r0 r1 into r2 as point;
+            output r2 as point.private;
+        */
+
+        let user_inputs = vec![U64(5), U64(6)];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r2 = function_variables["r2"].as_ref().unwrap();
+        assert!(matches!(r2, lambdavm::CircuitIOType::SimpleStruct(_)));
+        assert_eq!(r2.value().unwrap(), "{ x: 5, y: 6 }".to_owned());
+    }
+
+    #[test]
+    fn test_interface_input_with_mismatched_member_type() {
+        let program_string = test_helpers::read_program("interface").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_1";
+
+        let mut members = StructMembersMap::new();
+        members.insert("x".to_owned(), U8(1));
+        members.insert("y".to_owned(), U64(2));
+        let user_inputs = vec![Struct(members)];
+
+        assert!(lambdavm::execute_function(&program, function_name, &user_inputs).is_err());
+    }
+
+    #[test]
+    fn test_check_user_inputs() {
+        let program_string = test_helpers::read_program("interface").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function = program
+            .get_function(&Identifier::from_str("hello_1").unwrap())
+            .unwrap();
+
+        assert!(
+            helpers::check_user_inputs(&program, &[], &function, &[Struct(point(1, 2))]).is_ok()
+        );
+
+        let error = helpers::check_user_inputs(&program, &[], &function, &[]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Function hello_1 expects 1 inputs but 0 were given"
+        );
+
+        let error = helpers::check_user_inputs(&program, &[], &function, &[U8(1)]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid input r0 of function hello_1: expected point.public but got 1u8"
+        );
+
+        let mut members = point(1, 2);
+        members.insert("y".to_owned(), U8(2));
+        let error =
+            helpers::check_user_inputs(&program, &[], &function, &[Struct(members)]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid input r0 of function hello_1: Mismatched type for member `y` of interface point, expected u64"
+        );
+    }
+}
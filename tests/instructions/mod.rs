@@ -5,10 +5,16 @@ mod and;
 #[cfg(test)]
 mod compare;
 #[cfg(test)]
+mod constant;
+#[cfg(test)]
 mod div;
 #[cfg(test)]
+mod external_record;
+#[cfg(test)]
 mod hash_psd2;
 #[cfg(test)]
+mod interface;
+#[cfg(test)]
 mod is_eq_neq;
 #[cfg(test)]
 mod mul;
@@ -19,10 +25,14 @@ mod nor;
 #[cfg(test)]
 mod or;
 #[cfg(test)]
+mod record_interface;
+#[cfg(test)]
 mod shl;
 #[cfg(test)]
 mod shr;
 #[cfg(test)]
+mod string;
+#[cfg(test)]
 mod sub;
 #[cfg(test)]
 mod ternary;
@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod record_interface_tests {
+    use crate::helpers::test_helpers;
+    use lambdavm::{
+        helpers,
+        jaleo::{
+            RecordEntriesMap, StructMembersMap,
+            UserInputValueType::{self, Address, Boolean, Struct, U32, U64},
+        },
+        CircuitIOType::{SimpleRecord, SimpleStruct, SimpleUInt32},
+    };
+    use snarkvm::prelude::{Parser, Program, Testnet3};
+
+    fn metadata(expiry: u32, transferable: bool) -> StructMembersMap {
+        let mut members = StructMembersMap::new();
+        members.insert("expiry".to_owned(), U32(expiry));
+        members.insert("transferable".to_owned(), Boolean(transferable));
+        members
+    }
+
+    fn ticket(owner: [u8; 63], meta: StructMembersMap) -> UserInputValueType {
+        let mut data = RecordEntriesMap::new();
+        data.insert("meta".to_owned(), Struct(meta).into());
+        test_helpers::input_record(owner, 0, data, helpers::random_nonce())
+    }
+
+    fn program() -> Program<Testnet3> {
+        let program_string = test_helpers::read_program("record_interface").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        program
+    }
+
+    #[test]
+    fn test_cast_struct_into_record_entry() {
+        let program = program();
+        let function_name = "hello_1";
+
+        /*
+        function hello_1:
+            input r0 as address.private;
+            input r1 as metadata.private;
+            cast r0 0u64 r1 into r2 as ticket.record;
+            output r2 as ticket.record;
+        */
+
+        let (_address_string, address) = test_helpers::address();
+        let user_inputs = vec![Address(address), Struct(metadata(10, true))];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r2 = function_variables["r2"].as_ref().unwrap();
+        assert!(matches!(r2, SimpleRecord(_)));
+        if let SimpleRecord(record) = r2 {
+            let meta = &record.entries["meta"];
+            assert!(matches!(meta, SimpleStruct(_)));
+            assert_eq!(
+                meta.value().unwrap(),
+                "{ expiry: 10, transferable: true }".to_owned()
+            );
+            assert!(meta.is_witness().unwrap());
+        } else {
+            panic!("r2 should be a record");
+        }
+    }
+
+    #[test]
+    fn test_nested_record_entry_member_access() {
+        let program = program();
+        let function_name = "hello_2";
+
+        /*
+        function hello_2:
+            input r0 as ticket.record;
+            add r0.meta.expiry 1u32 into r1;
+            output r1 as u32.private;
+        */
+
+        let (_address_string, address) = test_helpers::address();
+        let user_inputs = vec![ticket(address, metadata(10, true))];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r1 = function_variables["r1"].as_ref().unwrap();
+        assert!(matches!(r1, SimpleUInt32(_)));
+        assert_eq!(r1.value().unwrap(), "11".to_owned());
+    }
+
+    #[test]
+    fn test_cast_nested_record_entry_members() {
+        let program = program();
+        let function_name = "hello_3";
+
+        /*
+        function hello_3:
+            input r0 as ticket.record;
+            input r1 as u32.private;
+            cast r1 r0.meta.transferable into r2 as metadata;
+            cast r0.owner r0.gates r2 into r3 as ticket.record;
+            output r3 as ticket.record;
+        */
+
+        let (_address_string, address) = test_helpers::address();
+        let user_inputs = vec![ticket(address, metadata(10, false)), U32(20)];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r3 = function_variables["r3"].as_ref().unwrap();
+        assert!(matches!(r3, SimpleRecord(_)));
+        if let SimpleRecord(record) = r3 {
+            assert_eq!(
+                record.entries["meta"].value().unwrap(),
+                "{ expiry: 20, transferable: false }".to_owned()
+            );
+        } else {
+            panic!("r3 should be a record");
+        }
+    }
+
+    #[test]
+    fn test_record_input_with_mismatched_struct_entry() {
+        let program = program();
+        let function_name = "hello_2";
+
+        let (_address_string, address) = test_helpers::address();
+        let mut meta = metadata(10, true);
+        meta.insert("expiry".to_owned(), U64(10));
+        let user_inputs = vec![ticket(address, meta)];
+
+        assert!(lambdavm::execute_function(&program, function_name, &user_inputs).is_err());
+    }
+
+    #[test]
+    fn test_cast_struct_of_another_interface_into_record_entry() {
+        let program = program();
+        let function_name = "hello_5";
+
+        /*
+        function hello_5:
+            input r0 as address.private;
+            input r1 as point.private;
+            cast r0 0u64 r1 into r2 as ticket.record;
+            output r2 as ticket.record;
+        */
+
+        let (_address_string, address) = test_helpers::address();
+        let mut point = StructMembersMap::new();
+        point.insert("x".to_owned(), U32(1));
+        point.insert("y".to_owned(), U32(2));
+        let user_inputs = vec![Address(address), Struct(point)];
+
+        let error = lambdavm::execute_function(&program, function_name, &user_inputs).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid entry `meta`: Expected member `expiry` of interface metadata but got `x`"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod string_tests {
+    use crate::helpers::test_helpers;
+    use lambdavm::jaleo::UserInputValueType::{Address, String};
+    use snarkvm::prelude::{Parser, Program, Testnet3};
+
+    #[test]
+    fn test_string_is_eq() {
+        let program_string = test_helpers::read_program("string").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_1";
+
+        /*
+        function hello_1:
+            input r0 as string.public;
+            input r1 as string.private;
+            is.eq r0 r1 into r2;
+            output r2 as boolean.public;
+        */
+
+        let user_inputs = vec![String("hello".to_owned()), String("hello".to_owned())];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r0 = function_variables["r0"].as_ref().unwrap();
+        assert!(matches!(r0, lambdavm::CircuitIOType::SimpleString(_)));
+        assert_eq!(r0.value().unwrap(), "hello".to_owned());
+
+        let r2 = function_variables["r2"].as_ref().unwrap();
+        assert!(matches!(r2, lambdavm::CircuitIOType::SimpleBoolean(_)));
+        assert_eq!(r2.value().unwrap(), "true".to_owned());
+    }
+
+    #[test]
+    fn test_string_is_neq_with_literal() {
+        let program_string = test_helpers::read_program("string").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_2";
+
+        /*
+        function hello_2:
+            input r0 as string.private;
+            is.neq r0 "aleo" into r1;
+            output r1 as boolean.private;
+        */
+
+        let user_inputs = vec![String("leo".to_owned())];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r1 = function_variables["r1"].as_ref().unwrap();
+        assert!(matches!(r1, lambdavm::CircuitIOType::SimpleBoolean(_)));
+        assert_eq!(r1.value().unwrap(), "true".to_owned());
+    }
+
+    #[test]
+    fn test_string_assert_eq_with_literal() {
+        let program_string = test_helpers::read_program("string").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_3";
+
+        /*
+        function hello_3:
+            input r0 as string.private;
+            assert.eq r0 "aleo";
+            output r0 as string.private;
+        */
+
+        let user_inputs = vec![String("aleo".to_owned())];
+        assert!(lambdavm::execute_function(&program, function_name, &user_inputs).is_ok());
+
+        let user_inputs = vec![String("leo".to_owned())];
+        assert!(lambdavm::execute_function(&program, function_name, &user_inputs).is_err());
+    }
+
+    #[test]
+    fn test_string_hash_psd2() {
+        let program_string = test_helpers::read_program("string").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_4";
+
+        /*
+        function hello_4:
+            input r0 as string.private;
+            hash.psd2 r0 into r1;
+            output r1 as field.private;
+        */
+
+        let user_inputs = vec![String("aleo".to_owned())];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r1 = function_variables["r1"].as_ref().unwrap();
+        assert!(matches!(r1, lambdavm::CircuitIOType::SimpleField(_)));
+    }
+
+    #[test]
+    fn test_cast_string_into_record_entry() {
+        let program_string = test_helpers::read_program("string").unwrap();
+        let (_, program) = Program::<Testnet3>::parse(&program_string).unwrap();
+        let function_name = "hello_5";
+
+        /*
+        function hello_5:
+            input r0 as address.private;
+            input r1 as string.private;
+            cast r0 0u64 r1 into r2 as note.record;
+            output r2 as note.record;
+        */
+
+        let (_address_string, address) = test_helpers::address();
+        let user_inputs = vec![Address(address), String("hello".to_owned())];
+
+        // execute circuit
+        let (function_variables, _proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let r2 = function_variables["r2"].as_ref().unwrap();
+        assert!(matches!(r2, lambdavm::CircuitIOType::SimpleRecord(_)));
+        if let lambdavm::CircuitIOType::SimpleRecord(record) = r2 {
+            let message = &record.entries["message"];
+            assert!(matches!(message, lambdavm::CircuitIOType::SimpleString(_)));
+            assert_eq!(message.value().unwrap(), "hello".to_owned());
+            assert!(message.is_witness().unwrap());
+        } else {
+            panic!("r2 should be a record");
+        }
+    }
+}
@@ -6,10 +6,12 @@ mod marlin_tests {
     use lambdavm::{
         build_program,
         jaleo::{
-            Identifier, Program,
-            UserInputValueType::{Boolean, U16, U8},
+            self, EncodedProof, EncodedVerifyingKey, Identifier, PrivateKey, Program,
+            RecordEntriesMap, RecordEntry, StructMembersMap,
+            UserInputValueType::{Address, Boolean, Struct, U16, U32, U64, U8},
         },
-        verify_proof,
+        universal_srs::UniversalSRSBounds,
+        verify_proof, verify_proof_with_constants, ProgramBuild,
     };
     use snarkvm::prelude::Parser;
     use std::str::FromStr;
@@ -40,6 +42,473 @@ mod marlin_tests {
         assert!(verify_proof(function_verifying_key.clone(), &user_inputs, &proof).unwrap())
     }
 
+    #[test]
+    fn test_proving_key_from_program_build_file() {
+        let program_string = test_helpers::read_program("add").unwrap();
+        let function_name = "hello_1";
+        let user_inputs = vec![U16(1), U16(1)];
+
+        let (program, program_build) = build_program(&program_string).unwrap();
+        let build_path = std::env::temp_dir().join("add.build.json");
+        program_build.write_to_file(&build_path).unwrap();
+        let program_build = ProgramBuild::read_from_file(&build_path).unwrap();
+        let (function_proving_key, function_verifying_key) =
+            program_build.get(function_name).unwrap();
+
+        // execute circuit with the stored proving key
+        let (_compiled_function_variables, proof, _constraint_counts) =
+            lambdavm::execute_function_with_proving_key(
+                &program,
+                &[],
+                function_name,
+                &user_inputs,
+                function_proving_key.clone(),
+            )
+            .unwrap();
+
+        assert!(verify_proof(function_verifying_key.clone(), &user_inputs, &proof).unwrap())
+    }
+
+    #[test]
+    fn test_program_build_binary_file() {
+        let program_string = test_helpers::read_program("add").unwrap();
+        let function_name = "hello_1";
+        let user_inputs = vec![U16(1), U16(1)];
+
+        let (program, program_build) = build_program(&program_string).unwrap();
+        let json_path = std::env::temp_dir().join("add_binary_test.build.json");
+        let binary_path = std::env::temp_dir().join("add_binary_test.build.bin");
+        program_build.write_to_file(&json_path).unwrap();
+        program_build.write_to_binary_file(&binary_path).unwrap();
+        assert!(
+            std::fs::metadata(&binary_path).unwrap().len()
+                < std::fs::metadata(&json_path).unwrap().len()
+        );
+
+        let program_build = ProgramBuild::read_from_file(&binary_path).unwrap();
+        let (function_proving_key, function_verifying_key) =
+            program_build.get(function_name).unwrap();
+        let (_compiled_function_variables, proof, _constraint_counts) =
+            lambdavm::execute_function_with_proving_key(
+                &program,
+                &[],
+                function_name,
+                &user_inputs,
+                function_proving_key.clone(),
+            )
+            .unwrap();
+
+        assert!(verify_proof(function_verifying_key.clone(), &user_inputs, &proof).unwrap())
+    }
+
+    #[test]
+    fn test_verify_execution() {
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+        let program_string = test_helpers::read_program("add").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let function_name = Identifier::from_str("hello_1").unwrap();
+        let private_key = PrivateKey::new(&mut rand::thread_rng()).unwrap();
+
+        let user_inputs = vec![U16(1), U16(1)];
+
+        let deployment = jaleo::generate_deployment(&program_string).unwrap();
+        let transitions =
+            jaleo::execution(&program, &function_name, &user_inputs, &private_key).unwrap();
+        let transition = transitions.first().unwrap();
+        assert!(jaleo::verify_execution(transition, &deployment).unwrap());
+
+        let transition_path = std::env::temp_dir().join("add_transition.bin");
+        transition.write_to_binary_file(&transition_path).unwrap();
+        let read_transition = jaleo::Transition::read_from_file(&transition_path).unwrap();
+        assert_eq!(read_transition.proof, transition.proof);
+        assert_eq!(read_transition.inputs, transition.inputs);
+        assert!(jaleo::verify_execution(&read_transition, &deployment).unwrap());
+
+        // Tampering with the public inputs invalidates the proof.
+        let mut tampered_transition = transition.clone();
+        tampered_transition.inputs = vec![
+            lambdavm::VariableType::Public(U16(2)),
+            lambdavm::VariableType::Public(U16(1)),
+        ];
+        assert!(!jaleo::verify_execution(&tampered_transition, &deployment).unwrap());
+    }
+
+    #[test]
+    fn test_verify_execution_with_public_record_entries() {
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+        let program_path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("programs/records.aleo");
+        let program_string = std::fs::read_to_string(program_path).unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let function_name = Identifier::from_str("mint").unwrap();
+        let private_key = PrivateKey::new(&mut rand::thread_rng()).unwrap();
+        let (_address_string, address) = test_helpers::address();
+        /*
+        function mint:
+            input r0 as u64.public;
+            input r1 as address.public;
+            cast r1 0u64 r0 into r2 as token.record;
+            output r2 as token.record;
+        */
+
+        let user_inputs = vec![U64(5), Address(address)];
+
+        let deployment = jaleo::generate_deployment(&program_string).unwrap();
+        let transitions =
+            jaleo::execution(&program, &function_name, &user_inputs, &private_key).unwrap();
+        let transition = transitions.first().unwrap();
+        assert!(jaleo::verify_execution(transition, &deployment).unwrap());
+
+        // The amount of the minted record is left in plaintext and is a
+        // public input of the proof.
+        let mut tampered_transition = transition.clone();
+        match tampered_transition.outputs.first_mut() {
+            Some(lambdavm::VariableType::EncryptedRecord((_commitment, encrypted_record))) => {
+                assert_eq!(
+                    encrypted_record.public_entries.get("amount"),
+                    Some(&RecordEntry::public(U64(5)))
+                );
+                encrypted_record
+                    .public_entries
+                    .insert("amount".to_owned(), RecordEntry::public(U64(6)));
+            }
+            _ => panic!("The output should be an encrypted record"),
+        }
+        assert!(!jaleo::verify_execution(&tampered_transition, &deployment).unwrap());
+    }
+
+    #[test]
+    fn test_verify_execution_with_poseidon_encrypted_records() {
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+        let program_path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("programs/records.aleo");
+        let program_string = std::fs::read_to_string(program_path).unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let function_name = Identifier::from_str("mint").unwrap();
+        let account = jaleo::Account::new(Some(0)).unwrap();
+        let address = lambdavm::helpers::to_address(account.address.to_string());
+
+        let user_inputs = vec![U64(5), Address(address)];
+
+        let deployment = jaleo::generate_deployment(&program_string).unwrap();
+        let transitions = jaleo::execution_with_imports(
+            &program,
+            &[],
+            &function_name,
+            &user_inputs,
+            &account.private_key,
+            jaleo::EncryptionScheme::Poseidon,
+        )
+        .unwrap();
+        let transition = transitions.first().unwrap();
+        assert!(jaleo::verify_execution(transition, &deployment).unwrap());
+
+        let (_commitment, encrypted_record) = transition.output_records().pop().unwrap();
+        assert_eq!(encrypted_record.scheme, jaleo::EncryptionScheme::Poseidon);
+        let records = transition.owned_records(&account.view_key);
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records.first().unwrap().1.data.get("amount"),
+            Some(&RecordEntry::public(U64(5)))
+        );
+    }
+
+    #[test]
+    fn test_verify_execution_with_records_output_in_another_order() {
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+        let program_path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("programs/records.aleo");
+        let program_string = std::fs::read_to_string(program_path).unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let function_name = Identifier::from_str("mint_two").unwrap();
+        let private_key = PrivateKey::new(&mut rand::thread_rng()).unwrap();
+        let (_address_string, address) = test_helpers::address();
+        /*
+        function mint_two:
+            input r0 as u64.public;
+            input r1 as address.public;
+            cast r1 0u64 r0 into r2 as token.record;
+            add r0 1u64 into r3;
+            cast r1 0u64 r3 into r4 as token.record;
+            cast r1 0u64 r3 into r5 as token.record;
+            output r4 as token.record;
+            output r2 as token.record;
+        */
+
+        let user_inputs = vec![U64(5), Address(address)];
+
+        let deployment = jaleo::generate_deployment(&program_string).unwrap();
+        let transitions =
+            jaleo::execution(&program, &function_name, &user_inputs, &private_key).unwrap();
+        let transition = transitions.first().unwrap();
+        let amounts = transition
+            .outputs
+            .iter()
+            .map(|output| match output {
+                lambdavm::VariableType::EncryptedRecord((_commitment, encrypted_record)) => {
+                    encrypted_record.public_entries.get("amount").cloned()
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            amounts,
+            vec![
+                Some(RecordEntry::public(U64(6))),
+                Some(RecordEntry::public(U64(5)))
+            ]
+        );
+        assert!(jaleo::verify_execution(transition, &deployment).unwrap());
+
+        // Swapping the outputs swaps their public entries, which must not
+        // verify.
+        let mut tampered_transition = transition.clone();
+        tampered_transition.outputs.swap(0, 1);
+        assert!(!jaleo::verify_execution(&tampered_transition, &deployment).unwrap());
+    }
+
+    #[test]
+    fn test_verify_execution_with_input_record_as_output() {
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+        let program_path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("programs/records.aleo");
+        let program_string = std::fs::read_to_string(program_path).unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let function_name = Identifier::from_str("forward").unwrap();
+        let private_key = PrivateKey::new(&mut rand::thread_rng()).unwrap();
+        let (_address_string, address) = test_helpers::address();
+        /*
+        function forward:
+            input r0 as token.record;
+            output r0 as token.record;
+        */
+
+        let mut data = RecordEntriesMap::new();
+        data.insert("amount".to_owned(), RecordEntry::public(U64(5)));
+        let user_inputs = vec![test_helpers::input_record(
+            address,
+            0,
+            data,
+            lambdavm::helpers::random_nonce(),
+        )];
+
+        let deployment = jaleo::generate_deployment(&program_string).unwrap();
+        let transitions =
+            jaleo::execution(&program, &function_name, &user_inputs, &private_key).unwrap();
+        let transition = transitions.first().unwrap();
+        assert!(jaleo::verify_execution(transition, &deployment).unwrap());
+    }
+
+    #[test]
+    fn test_verify_execution_with_public_struct_record_entry() {
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+        let program_string = test_helpers::read_program("record_interface").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let function_name = Identifier::from_str("hello_4").unwrap();
+        let private_key = PrivateKey::new(&mut rand::thread_rng()).unwrap();
+        let (_address_string, address) = test_helpers::address();
+        /*
+        function hello_4:
+            input r0 as address.private;
+            input r1 as metadata.private;
+            cast r0 0u64 r1 into r2 as pass.record;
+            output r2 as pass.record;
+        */
+
+        let mut meta = StructMembersMap::new();
+        meta.insert("expiry".to_owned(), U32(10));
+        meta.insert("transferable".to_owned(), Boolean(true));
+        let user_inputs = vec![Address(address), Struct(meta.clone())];
+
+        let deployment = jaleo::generate_deployment(&program_string).unwrap();
+        let transitions =
+            jaleo::execution(&program, &function_name, &user_inputs, &private_key).unwrap();
+        let transition = transitions.first().unwrap();
+        assert!(jaleo::verify_execution(transition, &deployment).unwrap());
+
+        // Every member of the public struct entry is a public input.
+        let mut tampered_transition = transition.clone();
+        match tampered_transition.outputs.first_mut() {
+            Some(lambdavm::VariableType::EncryptedRecord((_commitment, encrypted_record))) => {
+                assert_eq!(
+                    encrypted_record.public_entries.get("meta"),
+                    Some(&RecordEntry::public(Struct(meta.clone())))
+                );
+                meta.insert("expiry".to_owned(), U32(11));
+                encrypted_record
+                    .public_entries
+                    .insert("meta".to_owned(), RecordEntry::public(Struct(meta)));
+            }
+            _ => panic!("The output should be an encrypted record"),
+        }
+        assert!(!jaleo::verify_execution(&tampered_transition, &deployment).unwrap());
+    }
+
+    #[test]
+    fn test_verify_execution_with_imports() {
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+        let program_string = test_helpers::read_program("external_record").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let import_string = test_helpers::read_import("external_record", "token.aleo").unwrap();
+        let (_, import) = Program::parse(&import_string).unwrap();
+        let imports = [import];
+        let function_name = Identifier::from_str("hello_1").unwrap();
+        let private_key = PrivateKey::new(&mut rand::thread_rng()).unwrap();
+        let (_address_string, address) = test_helpers::address();
+        /*
+        function hello_1:
+            input r0 as token.aleo/token.record;
+            input r1 as u64.public;
+            add r0.amount r1 into r2;
+            output r2 as u64.public;
+        */
+
+        let mut data = RecordEntriesMap::new();
+        data.insert("amount".to_owned(), U64(1).into());
+        let user_inputs = vec![
+            test_helpers::input_record(address, 0, data, lambdavm::helpers::random_nonce()),
+            U64(2),
+        ];
+
+        assert!(jaleo::generate_deployment(&program_string).is_err());
+        let deployment =
+            jaleo::generate_deployment_with_imports(&program_string, &imports).unwrap();
+        assert!(jaleo::check_deployment_with_imports(&deployment, &imports).is_ok());
+
+        let transitions = jaleo::execution_with_imports(
+            &program,
+            &imports,
+            &function_name,
+            &user_inputs,
+            &private_key,
+            jaleo::EncryptionScheme::default(),
+        )
+        .unwrap();
+        let transition = transitions.first().unwrap();
+        assert!(jaleo::verify_execution_with_imports(transition, &deployment, &imports).unwrap());
+    }
+
+    #[test]
+    fn test_check_deployment() {
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+        let program_string = test_helpers::read_program("add").unwrap();
+
+        let deployment = jaleo::generate_deployment(&program_string).unwrap();
+        let serialized_deployment = serde_json::to_string(&deployment).unwrap();
+        let deployment: jaleo::Deployment = serde_json::from_str(&serialized_deployment).unwrap();
+        assert!(jaleo::check_deployment(&deployment).is_ok());
+
+        let deployment_path = std::env::temp_dir().join("add_deployment.bin");
+        deployment.write_to_binary_file(&deployment_path).unwrap();
+        let deployment = jaleo::Deployment::read_from_file(&deployment_path).unwrap();
+        assert!(jaleo::check_deployment(&deployment).is_ok());
+
+        let mut incomplete_deployment = deployment;
+        incomplete_deployment.verifying_keys.map.clear();
+        assert!(jaleo::check_deployment(&incomplete_deployment).is_err());
+    }
+
+    #[test]
+    fn test_constant_inputs() {
+        let program_string = test_helpers::read_program("constant").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let function_name = "hello_1";
+        /*
+        function hello_1:
+            input r0 as u8.constant;
+            input r1 as u8.public;
+            add r0 r1 into r2;
+            output r2 as u8.public;
+        */
+
+        let constants = vec![U8(1)];
+        let public_inputs = vec![U8(2)];
+        let user_inputs = vec![U8(1), U8(2)];
+
+        // execute circuit
+        let (_compiled_function_variables, proof) =
+            lambdavm::execute_function(&program, function_name, &user_inputs).unwrap();
+
+        let universal_srs = lambdavm::generate_universal_srs().unwrap();
+        let function_identifier = Identifier::from_str(function_name).unwrap();
+        assert!(verify_proof_with_constants(
+            &program,
+            &[],
+            &function_identifier,
+            &constants,
+            &public_inputs,
+            &proof,
+            &universal_srs
+        )
+        .unwrap());
+
+        // A proof generated with other constants does not verify.
+        let other_constants = vec![U8(3)];
+        assert!(!matches!(
+            verify_proof_with_constants(
+                &program,
+                &[],
+                &function_identifier,
+                &other_constants,
+                &public_inputs,
+                &proof,
+                &universal_srs
+            ),
+            Ok(true)
+        ));
+    }
+
+    #[test]
+    fn test_stored_keys_are_rejected_for_constant_inputs() {
+        let program_string = test_helpers::read_program("constant").unwrap();
+        let function_name = "hello_1";
+        let user_inputs = vec![U8(3), U8(2)];
+
+        let (program, program_build) = build_program(&program_string).unwrap();
+        let (function_proving_key, _function_verifying_key) =
+            program_build.get(function_name).unwrap();
+
+        // The stored keys were built with the default constants.
+        let error = lambdavm::execute_function_with_proving_key(
+            &program,
+            &[],
+            function_name,
+            &user_inputs,
+            function_proving_key.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Function hello_1 has constant inputs, its proofs need keys built for the given constants instead of stored ones"
+        );
+    }
+
+    #[test]
+    fn test_verify_execution_checks_the_inputs_of_the_function() {
+        let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+        let program_string = test_helpers::read_program("constant").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let function_name = Identifier::from_str("hello_1").unwrap();
+        let private_key = PrivateKey::new(&mut rand::thread_rng()).unwrap();
+        let user_inputs = vec![U8(3), U8(2)];
+
+        let deployment = jaleo::generate_deployment(&program_string).unwrap();
+        let transitions =
+            jaleo::execution(&program, &function_name, &user_inputs, &private_key).unwrap();
+        let transition = transitions.first().unwrap();
+        assert!(jaleo::verify_execution(transition, &deployment).unwrap());
+
+        // Leaving out the constants must not get the proof checked against
+        // the deployed keys, which were built with default constants.
+        let mut tampered_transition = transition.clone();
+        tampered_transition.inputs.remove(0);
+        let error = jaleo::verify_execution(&tampered_transition, &deployment).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Function hello_1 takes 2 inputs but the transition has 1"
+        );
+    }
+
     #[test]
     fn test_subtract() {
         let program_string = test_helpers::read_program("subtract").unwrap();
@@ -327,4 +796,53 @@ mod marlin_tests {
             program_build.map.get(&function_identifier).unwrap();
         assert!(verify_proof(function_verifying_key.clone(), &user_inputs, &proof).unwrap())
     }
+
+    #[test]
+    fn test_function_constraint_counts() {
+        let program_string = test_helpers::read_program("add").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let user_inputs = vec![U16(1), U16(1)];
+
+        let constraint_counts =
+            lambdavm::function_constraint_counts(&program, &[], "hello_1", &user_inputs).unwrap();
+        let bounds = constraint_counts.universal_srs_bounds();
+
+        assert!(constraint_counts.non_zero > 0);
+        assert_eq!(
+            bounds.num_variables,
+            constraint_counts.instance_variables.next_power_of_two()
+                + constraint_counts.witness_variables
+        );
+        assert!(UniversalSRSBounds::DEFAULT.contains(&bounds));
+        assert!(!bounds.contains(&UniversalSRSBounds::DEFAULT));
+    }
+
+    #[test]
+    fn test_bech32m_encoded_proof() {
+        let program_string = test_helpers::read_program("add").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let user_inputs = vec![U16(1), U16(1)];
+
+        let (_compiled_function_variables, proof) =
+            lambdavm::execute_function(&program, "hello_1", &user_inputs).unwrap();
+        let (_program, program_build) = build_program(&program_string).unwrap();
+        let function_identifier = Identifier::from_str("hello_1").unwrap();
+        let (_function_proving_key, function_verifying_key) =
+            program_build.map.get(&function_identifier).unwrap();
+
+        let encoded_proof = EncodedProof(proof).to_string();
+        assert!(encoded_proof.starts_with("proof1"));
+        let decoded_proof = EncodedProof::from_str(&encoded_proof).unwrap().0;
+        assert!(
+            verify_proof(function_verifying_key.clone(), &user_inputs, &decoded_proof).unwrap()
+        );
+
+        let truncated_proof = encoded_proof.get(..encoded_proof.len() - 1).unwrap();
+        assert!(EncodedProof::from_str(truncated_proof).is_err());
+
+        let encoded_verifying_key = EncodedVerifyingKey(function_verifying_key.clone()).to_string();
+        assert!(encoded_verifying_key.starts_with("verifier1"));
+        assert!(EncodedVerifyingKey::from_str(&encoded_verifying_key).is_ok());
+        assert!(EncodedProof::from_str(&encoded_verifying_key).is_err());
+    }
 }
@@ -1,2 +1,9 @@
 pub mod helpers;
 mod instructions;
+
+#[ctor::ctor]
+fn init() {
+    // generate universal srs file before running tests, executing a function
+    // builds its keys from it
+    let _ = lambdavm::universal_srs::generate_universal_srs_and_write_to_file();
+}
@@ -0,0 +1,51 @@
+mod helpers;
+
+#[cfg(test)]
+mod universal_srs_ceremony_tests {
+    use super::helpers::test_helpers;
+    use lambdavm::{
+        ceremony,
+        jaleo::{self, Identifier, PrivateKey, Program, UserInputValueType::U16},
+        universal_srs::{self, UniversalSRSBounds, UNIVERSAL_SRS_PATH_ENV},
+    };
+    use snarkvm::prelude::Parser;
+    use std::str::FromStr;
+
+    const BOUNDS: UniversalSRSBounds = UniversalSRSBounds {
+        num_constraints: 5000,
+        num_variables: 2000,
+        num_non_zero: 10000,
+    };
+
+    // This is the only test of this file because it changes where the
+    // universal parameters are loaded from for the whole process.
+    #[test]
+    fn test_execute_with_contributed_universal_srs() {
+        let dir = std::env::temp_dir().join("lambdavm_test_ceremony_execution");
+        let initial_path = dir.join("universal_srs_0");
+        let contributed_path = dir.join("universal_srs_1");
+        let transcript_path = dir.join("transcript.json");
+        let _ = std::fs::remove_file(&transcript_path);
+        universal_srs::generate_universal_srs_and_write_to_path(&initial_path, &BOUNDS).unwrap();
+        ceremony::contribute_to_file(&initial_path, &contributed_path, &transcript_path).unwrap();
+
+        let program_string = test_helpers::read_program("add").unwrap();
+        let (_, program) = Program::parse(&program_string).unwrap();
+        let function_name = Identifier::from_str("hello_1").unwrap();
+        let private_key = PrivateKey::new(&mut rand::thread_rng()).unwrap();
+        let user_inputs = vec![U16(1), U16(1)];
+
+        std::env::set_var(UNIVERSAL_SRS_PATH_ENV, &contributed_path);
+        let deployment = jaleo::generate_deployment(&program_string).unwrap();
+        let transitions =
+            jaleo::execution(&program, &function_name, &user_inputs, &private_key).unwrap();
+        let transition = transitions.first().unwrap();
+        assert!(jaleo::verify_execution(transition, &deployment).unwrap());
+
+        // The keys built from the parameters before the contribution are
+        // different, so the proof does not verify against them.
+        std::env::set_var(UNIVERSAL_SRS_PATH_ENV, &initial_path);
+        let initial_deployment = jaleo::generate_deployment(&program_string).unwrap();
+        assert!(!jaleo::verify_execution(transition, &initial_deployment).unwrap_or(false));
+    }
+}